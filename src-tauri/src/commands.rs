@@ -19,25 +19,57 @@ mod database;
 mod environment;
 mod explorer;
 mod filesystem;
+mod frontmatter;
+mod graph;
+mod history;
+mod journal;
+mod links;
 mod notes;
 mod plugin_builder;
 mod plugins;
+mod quick_open;
+mod search;
 mod settings;
 mod side_repos;
+mod subscriptions;
+mod sync;
 mod terminal;
 mod themes;
+mod transfer;
+mod trash;
+mod vault_config;
+mod vaults;
 mod video;
+mod voidignore;
+mod watcher;
+mod workspace;
 pub use audio::*;
 pub use canvas::*;
 pub use database::*;
 pub use environment::*;
 pub use explorer::*;
 pub use filesystem::*;
+pub use frontmatter::*;
+pub use graph::*;
+pub use history::*;
+pub use journal::*;
+pub use links::*;
 pub use notes::*;
 pub use plugin_builder::*;
 pub use plugins::*;
+pub use quick_open::*;
+pub use search::*;
 pub use settings::*;
 pub use side_repos::*;
+pub use subscriptions::*;
+pub use sync::*;
 pub use terminal::*;
 pub use themes::*;
+pub use transfer::*;
+pub use trash::*;
+pub use vault_config::*;
+pub use vaults::*;
 pub use video::*;
+pub use voidignore::*;
+pub use watcher::*;
+pub use workspace::*;
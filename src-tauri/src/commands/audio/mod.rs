@@ -24,8 +24,8 @@ pub struct AudioMeta {
 }
 
 #[tauri::command]
-pub fn read_audio_metadata(path: String) -> Result<AudioMeta, String> {
-    let path = std::path::Path::new(&path);
+pub async fn read_audio_metadata(path: String) -> Result<AudioMeta, String> {
+    let path = super::resolve_readable_path(&path).await?;
     let tags = lofty::probe::Probe::open(path)
         .map_err(|e| e.to_string())?
         .guess_file_type()
@@ -21,10 +21,9 @@ pub async fn write_canvas_data(
     data: String,
     app: tauri::AppHandle,
 ) -> Result<String, String> {
-    let workdir = super::get_env("workdir".to_string()).await.unwrap();
     let mut path = path;
     if path.is_empty() {
-        let canvas_collection = super::get_directory_content("".to_string()).await;
+        let canvas_collection = super::get_directory_content("".to_string(), None).await;
         let index = canvas_collection
             .unwrap()
             .iter()
@@ -34,13 +33,21 @@ pub async fn write_canvas_data(
             + 1;
         path = format!("/untitled{}.canvas", index);
     }
-    let path = workdir + path.as_str();
-    let path = std::path::Path::new(&path);
+    let workdir = super::workdir().await?;
+    let canvas = super::resolve_in(&workdir, &path)?;
+    let path = canvas.absolute.as_path();
     println!("{:#?}", path);
+    let previous = std::fs::read_to_string(path).ok();
     std::fs::write(path, data.as_str()).map_err(|e| {
         app.emit("error", e.to_string()).unwrap();
         e.to_string()
     })?;
+    super::entry_updated(&workdir, &canvas.relative).await;
+    let inverse = super::FsAction::Write {
+        path: canvas.relative.clone(),
+        content: previous,
+    };
+    super::record_fs_op(&workdir, "write", vec![inverse]);
     Ok(path
         .to_str()
         .unwrap()
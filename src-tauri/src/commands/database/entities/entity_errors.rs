@@ -31,4 +31,10 @@ pub enum EntityError {
 
     #[error("Нельзя удалить основную конфигурацию")]
     NotAllowed,
+
+    #[error("Неизвестное поле `{0}`")]
+    UnknownField(String),
+
+    #[error("Удаление без условий запрещено")]
+    UnfilteredDelete,
 }
@@ -0,0 +1,111 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+use serde_json::Value;
+use url::Url;
+
+use super::EntityError;
+
+// A single typed column of an entity, as written by `DbRepo::update`.
+pub trait EntityField: Sized {
+    fn key(&self) -> &'static str;
+    fn value(&self) -> Result<Value, EntityError>;
+    // Reads the column `key` from `value` the same way it is read from a stored record.
+    fn from_key(key: &str, value: Value) -> Result<Self, EntityError>;
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredFlag {
+    Bool(bool),
+    Text(String),
+}
+
+// Records written before flags were typed keep them as "true"/"false" strings.
+pub fn deserialize_flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    match StoredFlag::deserialize(deserializer)? {
+        StoredFlag::Bool(flag) => Ok(flag),
+        StoredFlag::Text(text) => text
+            .parse()
+            .map_err(|_| D::Error::custom(format!("некорректный флаг `{}`", text))),
+    }
+}
+
+// Repository links are entered without a scheme ("github.com/user/repo"),
+// official plugins have no link at all.
+pub fn parse_link(link: &str) -> Result<Option<Url>, url::ParseError> {
+    match link.trim() {
+        "" | "none" => Ok(None),
+        link if link.contains("://") => Url::parse(link).map(Some),
+        link => Url::parse(&format!("https://{}", link)).map(Some),
+    }
+}
+
+pub fn deserialize_link<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Url>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(link) => parse_link(&link).map_err(D::Error::custom),
+        None => Ok(None),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntityVersion {
+    Latest,
+    Release(semver::Version),
+    // Manifests are not required to follow semver.
+    Unknown(String),
+}
+
+impl From<&str> for EntityVersion {
+    fn from(version: &str) -> Self {
+        let version = version.trim();
+        if version == "latest" {
+            return EntityVersion::Latest;
+        }
+        match semver::Version::parse(version.trim_start_matches('v')) {
+            Ok(release) => EntityVersion::Release(release),
+            Err(_) => EntityVersion::Unknown(version.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for EntityVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntityVersion::Latest => write!(f, "latest"),
+            EntityVersion::Release(release) => write!(f, "{}", release),
+            EntityVersion::Unknown(version) => write!(f, "{}", version),
+        }
+    }
+}
+
+impl Serialize for EntityVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(EntityVersion::from(
+            String::deserialize(deserializer)?.as_str(),
+        ))
+    }
+}
@@ -13,13 +13,37 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use super::EntityError;
+use super::{EntityError, EntityField};
+use serde_json::Value;
 use std::result::Result;
 use tauri::Emitter;
 
+pub use tauri::AppHandle;
+pub use void_macros::Entity;
+
+// Describes one stored column, generated by `#[derive(Entity)]`.
+pub struct FieldMeta {
+    pub key: &'static str,
+    pub ty: &'static str,
+    pub required: bool,
+}
+
+pub trait Entity {
+    type Fields: EntityField;
+    const TABLE: &'static str;
+    const FIELDS: &'static [FieldMeta];
+}
+
 pub trait EntityControl<B, T> {
     fn new(input: Vec<B>, app: tauri::AppHandle) -> Result<T, EntityError>;
-    fn get_value_by_key(&self, key: String) -> Result<String, EntityError>;
+    fn get_value(&self, key: &str) -> Result<Value, EntityError>;
+    fn get_value_by_key(&self, key: String) -> Result<String, EntityError> {
+        match self.get_value(&key)? {
+            Value::String(s) => Ok(s),
+            Value::Null => Err(EntityError::NotFound),
+            value => Ok(value.to_string()),
+        }
+    }
     fn throw_error(app: tauri::AppHandle, err_val: &'static str) -> EntityError {
         let _ = app.emit("error", err_val);
         EntityError::InvalidField(err_val)
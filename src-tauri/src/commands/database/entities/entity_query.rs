@@ -0,0 +1,312 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::marker::PhantomData;
+
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::Value;
+use surrealdb::{Notification, Response, method::QueryStream};
+
+use super::{DbRepo, Entity, EntityError, EntityField, MainConfig};
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Cmp {
+    #[default]
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+impl Cmp {
+    fn operator(self) -> &'static str {
+        match self {
+            Cmp::Eq => "=",
+            Cmp::Ne => "!=",
+            Cmp::Lt => "<",
+            Cmp::Le => "<=",
+            Cmp::Gt => ">",
+            Cmp::Ge => ">=",
+            Cmp::Contains => "CONTAINS",
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ListFilter {
+    pub key: String,
+    #[serde(default)]
+    pub cmp: Cmp,
+    pub value: Value,
+}
+
+// Listing options sent by the frontend, applied with `EntityQuery::apply`.
+#[derive(Deserialize, Clone, Default)]
+pub struct ListQuery {
+    #[serde(default)]
+    pub filters: Vec<ListFilter>,
+    pub order_by: Option<String>,
+    #[serde(default)]
+    pub order: Order,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+// Builds a SurrealQL statement for one entity table. Column names only ever come
+// from the entity's generated field list and values are always bound, so nothing
+// the caller passes is spliced into the query text.
+pub struct EntityQuery<'a, T: Entity> {
+    repo: &'a DbRepo,
+    filters: Vec<(&'static str, Cmp, Value)>,
+    order: Vec<(&'static str, Order)>,
+    projection: Vec<&'static str>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    // The first invalid field name, reported when the query runs.
+    error: Option<EntityError>,
+    entity: PhantomData<T>,
+}
+
+fn known_field<T: Entity>(key: &str) -> Result<&'static str, EntityError> {
+    T::FIELDS
+        .iter()
+        .find(|field| field.key == key)
+        .map(|field| field.key)
+        .ok_or_else(|| EntityError::UnknownField(key.to_string()))
+}
+
+impl<'a, T: Entity> EntityQuery<'a, T> {
+    pub fn new(repo: &'a DbRepo) -> Self {
+        EntityQuery {
+            repo,
+            filters: Vec::new(),
+            order: Vec::new(),
+            projection: Vec::new(),
+            limit: None,
+            offset: None,
+            error: None,
+            entity: PhantomData,
+        }
+    }
+
+    fn check(&mut self, key: &str) -> Option<&'static str> {
+        match known_field::<T>(key) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                self.error.get_or_insert(e);
+                None
+            }
+        }
+    }
+
+    pub fn filter(self, field: T::Fields) -> Self {
+        self.filter_cmp(field, Cmp::Eq)
+    }
+
+    pub fn filter_cmp(mut self, field: T::Fields, cmp: Cmp) -> Self {
+        match field.value() {
+            Ok(value) => self.filters.push((field.key(), cmp, value)),
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+        self
+    }
+
+    pub fn order_by(mut self, key: &str, order: Order) -> Self {
+        if let Some(key) = self.check(key) {
+            self.order.push((key, order));
+        }
+        self
+    }
+
+    pub fn select(mut self, keys: &[&str]) -> Self {
+        for key in keys {
+            if let Some(key) = self.check(key) {
+                self.projection.push(key);
+            }
+        }
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    // Filter values are read with the field's own type, so `"true"` and `true`
+    // both match a flag while `"yes"` is rejected.
+    pub fn apply(mut self, list: ListQuery) -> Self {
+        for filter in list.filters {
+            let Some(key) = self.check(&filter.key) else {
+                continue;
+            };
+            match T::Fields::from_key(key, filter.value) {
+                Ok(field) => self = self.filter_cmp(field, filter.cmp),
+                Err(e) => {
+                    self.error.get_or_insert(e);
+                }
+            }
+        }
+        if let Some(key) = list.order_by {
+            self = self.order_by(&key, list.order);
+        }
+        if let Some(limit) = list.limit {
+            self = self.limit(limit);
+        }
+        if let Some(offset) = list.offset {
+            self = self.offset(offset);
+        }
+        self
+    }
+
+    fn where_clause(&self) -> String {
+        if self.filters.is_empty() {
+            return String::new();
+        }
+        let filters = self
+            .filters
+            .iter()
+            .enumerate()
+            .map(|(i, (key, cmp, _))| format!("{} {} $p{}", key, cmp.operator(), i))
+            .collect::<Vec<_>>();
+        format!(" WHERE {}", filters.join(" AND "))
+    }
+
+    fn page_clause(&self) -> String {
+        let mut sql = String::new();
+        if !self.order.is_empty() {
+            let order = self
+                .order
+                .iter()
+                .map(|(key, order)| match order {
+                    Order::Asc => format!("{} ASC", key),
+                    Order::Desc => format!("{} DESC", key),
+                })
+                .collect::<Vec<_>>();
+            sql.push_str(&format!(" ORDER BY {}", order.join(", ")));
+        }
+        if let Some(limit) = self.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+        if let Some(offset) = self.offset {
+            sql.push_str(&format!(" START {}", offset));
+        }
+        sql
+    }
+
+    async fn run(&self, sql: String) -> Result<Response, EntityError> {
+        let mut query = self.repo.database.query(sql);
+        for (i, (_, _, value)) in self.filters.iter().enumerate() {
+            query = query.bind((format!("p{}", i), value.clone()));
+        }
+        query
+            .await
+            .and_then(|response| response.check())
+            .map_err(|_| EntityError::DbQueryError)
+    }
+
+    pub async fn fetch(self) -> Result<Vec<T>, EntityError>
+    where
+        T: DeserializeOwned,
+    {
+        self.fetch_as::<T>().await
+    }
+
+    pub async fn first(self) -> Result<Option<T>, EntityError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(self.limit(1).fetch().await?.into_iter().next())
+    }
+
+    // Reads only the selected columns into `P`.
+    pub async fn fetch_as<P: DeserializeOwned>(mut self) -> Result<Vec<P>, EntityError> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        let projection = match self.projection.is_empty() {
+            true => "*".to_string(),
+            false => {
+                // SurrealDB can only order by columns that are part of the selection.
+                let mut keys = self.projection.clone();
+                for (key, _) in &self.order {
+                    if !keys.contains(key) {
+                        keys.push(*key);
+                    }
+                }
+                keys.join(", ")
+            }
+        };
+        let sql = format!(
+            "SELECT {} FROM {}{}{}",
+            projection,
+            T::TABLE,
+            self.where_clause(),
+            self.page_clause()
+        );
+        self.run(sql)
+            .await?
+            .take::<Vec<P>>(0)
+            .map_err(|_| EntityError::DbQueryError)
+    }
+
+    // Live queries cannot be ordered or paged, so only the filters are used.
+    pub async fn live(mut self) -> Result<QueryStream<Notification<T>>, EntityError>
+    where
+        T: DeserializeOwned + Unpin,
+    {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        let sql = format!("LIVE SELECT * FROM {}{}", T::TABLE, self.where_clause());
+        self.run(sql)
+            .await?
+            .stream::<Notification<T>>(0)
+            .map_err(|_| EntityError::DbQueryError)
+    }
+
+    pub async fn delete(mut self) -> Result<(), EntityError> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        if T::TABLE == MainConfig::TABLE {
+            return Err(EntityError::NotAllowed);
+        }
+        if self.filters.is_empty() {
+            return Err(EntityError::UnfilteredDelete);
+        }
+        let sql = format!("DELETE FROM {}{}", T::TABLE, self.where_clause());
+        self.run(sql).await?;
+        Ok(())
+    }
+}
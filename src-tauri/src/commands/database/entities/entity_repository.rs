@@ -16,9 +16,10 @@
 use std::fmt::Debug;
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use surrealdb::{Surreal, engine::local::Db};
 
-use super::{EntityControl, EntityError};
+use super::{Entity, EntityControl, EntityError, EntityField, EntityQuery};
 
 pub struct DbRepo {
     pub database: Surreal<Db>,
@@ -29,6 +30,10 @@ impl DbRepo {
         DbRepo { database: db }
     }
 
+    pub fn query<T: Entity>(&self) -> EntityQuery<'_, T> {
+        EntityQuery::new(self)
+    }
+
     pub async fn create<B, T>(
         &self,
         input: Vec<B>,
@@ -67,40 +72,22 @@ impl DbRepo {
         Ok(result)
     }
 
-    pub async fn update(
+    // Only the column named by `field` changes; its value goes through serde
+    // instead of being formatted into the query.
+    pub async fn update<F: EntityField>(
         &self,
-        name: String,
+        name: &str,
         db_name: &'static str,
-        key: String,
-        value: String,
+        field: F,
     ) -> Result<(), EntityError> {
-        let query = format!("UPDATE type::thing($table, $name) SET {} = $value", key);
-        let _ = self
-            .database
-            .query(query)
+        let patch = Map::from_iter([(field.key().to_string(), field.value()?)]);
+        self.database
+            .query("UPDATE type::thing($table, $name) MERGE $patch")
             .bind(("table", db_name))
-            .bind(("name", name))
-            .bind(("value", value))
-            .await
-            .map_err(|_| EntityError::DbQueryError)?;
-        Ok(())
-    }
-
-    pub async fn delete(
-        &self,
-        db_name: &'static str,
-        key: &'static str,
-        value: String,
-    ) -> Result<(), EntityError> {
-        if db_name == "main_config" {
-            return Err(EntityError::NotAllowed);
-        }
-        let query = format!("DELETE FROM {} WHERE {} = $value", db_name, key);
-        let _ = &self
-            .database
-            .query(query)
-            .bind(("value", value))
+            .bind(("name", name.to_string()))
+            .bind(("patch", Value::Object(patch)))
             .await
+            .and_then(|response| response.check())
             .map_err(|_| EntityError::DbQueryError)?;
         Ok(())
     }
@@ -13,67 +13,14 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use super::{EntityControl, EntityError};
+use super::{Entity, deserialize_flag};
 use serde::{Deserialize, Serialize};
-pub enum MainConfigFields {
-    Name(String),
-    FirstRun(String),
-    Workdir(String),
-}
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Entity)]
+#[entity(table = "main_config")]
 pub struct MainConfig {
-    name: String,
-    first_run: String,
-    workdir: String,
-}
-
-impl EntityControl<MainConfigFields, MainConfig> for MainConfig {
-    fn new(input: Vec<MainConfigFields>, app: tauri::AppHandle) -> Result<MainConfig, EntityError> {
-        if input.len() != 3 {
-            return Err(EntityError::WrongInputLength);
-        }
-        let name = match input.first() {
-            Some(MainConfigFields::Name(name)) => name.clone(),
-            _ => {
-                return Err(MainConfig::throw_error(
-                    app.clone(),
-                    "Некорректное поле Name",
-                ));
-            }
-        };
-        let first_run = match input.get(1) {
-            Some(MainConfigFields::FirstRun(used)) => used.clone(),
-            _ => {
-                return Err(MainConfig::throw_error(
-                    app.clone(),
-                    "Некорректное поле FirstRun",
-                ));
-            }
-        };
-        let workdir = match input.get(2) {
-            Some(MainConfigFields::Workdir(dir)) => dir.clone(),
-            _ => {
-                return Err(MainConfig::throw_error(
-                    app.clone(),
-                    "Некорректное поле Workdir",
-                ));
-            }
-        };
-
-        Ok(MainConfig {
-            name,
-            first_run,
-            workdir,
-        })
-    }
-
-    fn get_value_by_key(&self, key: String) -> Result<String, EntityError> {
-        match key.as_str() {
-            "name" => Ok(self.name.clone()),
-            "first_run" => Ok(self.first_run.clone().to_string()),
-            "workdir" => Ok(self.workdir.clone()),
-            _ => Err(EntityError::NotFound),
-        }
-    }
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_flag")]
+    pub first_run: bool,
+    pub workdir: String,
 }
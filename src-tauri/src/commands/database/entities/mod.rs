@@ -14,17 +14,23 @@
  * limitations under the License.
  */
 mod entity_errors;
+mod entity_fields;
 mod entity_interface;
+mod entity_query;
 mod entity_repository;
 mod main_config;
 mod plugin_list;
 mod side_repos;
 mod themes_list;
+mod vault;
 
 pub use entity_errors::*;
+pub use entity_fields::*;
 pub use entity_interface::*;
+pub use entity_query::*;
 pub use entity_repository::*;
 pub use main_config::*;
 pub use plugin_list::*;
 pub use side_repos::*;
 pub use themes_list::*;
+pub use vault::*;
@@ -13,53 +13,12 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use super::{EntityControl, EntityError};
+use super::Entity;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
-pub enum SideRepoField {
-    Type(String),
-    Link(String),
-}
-
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Entity)]
+#[entity(table = "side_repo")]
 pub struct SideRepo {
-    repo_type: String,
-    link: String,
-}
-
-impl EntityControl<SideRepoField, SideRepo> for SideRepo {
-    fn new(input: Vec<SideRepoField>, app: tauri::AppHandle) -> Result<SideRepo, EntityError> {
-        let repo_type = match input.first() {
-            Some(SideRepoField::Type(s)) => s.clone(),
-            _ => {
-                SideRepo::throw_error(app.clone(), "Type");
-                "?".to_string()
-            }
-        };
-        let link = match input.get(1) {
-            Some(SideRepoField::Link(s)) => s.clone(),
-            _ => {
-                SideRepo::throw_error(app.clone(), "Link");
-                "?".to_string()
-            }
-        };
-        Ok(SideRepo { repo_type, link })
-    }
-    fn get_value_by_key(&self, key: String) -> Result<String, EntityError> {
-        match key.as_str() {
-            "type" => Ok(self.repo_type.clone()),
-            "link" => Ok(self.link.clone()),
-            _ => Err(EntityError::NotFound),
-        }
-    }
-}
-
-impl Clone for SideRepo {
-    fn clone(&self) -> Self {
-        Self {
-            link: self.link.clone(),
-            repo_type: self.repo_type.clone(),
-        }
-    }
+    pub repo_type: String,
+    pub link: String,
 }
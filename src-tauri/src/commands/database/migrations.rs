@@ -0,0 +1,192 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{path::PathBuf, sync::Mutex};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Surreal, engine::local::Db};
+use thiserror::Error;
+
+use crate::{MAIN_FOLDER_PREFIX, commands::now_millis};
+
+struct Migration {
+    name: &'static str,
+    statements: &'static str,
+}
+
+// Append only: a step's position in the list is the schema version it produces,
+// so shipped steps must never be edited or reordered.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        name: "define_tables",
+        statements: "
+            DEFINE TABLE IF NOT EXISTS main_config SCHEMALESS;
+            DEFINE TABLE IF NOT EXISTS plugins_repo SCHEMALESS;
+            DEFINE TABLE IF NOT EXISTS themes_repo SCHEMALESS;
+            DEFINE TABLE IF NOT EXISTS side_repo SCHEMALESS;
+            DEFINE TABLE IF NOT EXISTS vault SCHEMALESS;
+            DEFINE TABLE IF NOT EXISTS setting SCHEMALESS;
+        ",
+    },
+    Migration {
+        name: "fill_missing_fields",
+        statements: "
+            UPDATE main_config SET name = '' WHERE name = NONE;
+            UPDATE main_config SET first_run = 'true' WHERE first_run = NONE;
+            UPDATE main_config SET workdir = '' WHERE workdir = NONE;
+            UPDATE plugins_repo SET is_installed = 'false' WHERE is_installed = NONE;
+            UPDATE plugins_repo SET is_enabled = 'false' WHERE is_enabled = NONE;
+            UPDATE themes_repo SET is_installed = 'false' WHERE is_installed = NONE;
+        ",
+    },
+    // Flags used to be "true"/"false" strings; anything but "true" becomes false.
+    Migration {
+        name: "typed_flags",
+        statements: "
+            UPDATE main_config SET first_run = (first_run = 'true') WHERE type::is::string(first_run);
+            UPDATE plugins_repo SET is_installed = (is_installed = 'true') WHERE type::is::string(is_installed);
+            UPDATE plugins_repo SET is_enabled = (is_enabled = 'true') WHERE type::is::string(is_enabled);
+            UPDATE themes_repo SET is_installed = (is_installed = 'true') WHERE type::is::string(is_installed);
+        ",
+    },
+];
+
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+static STATUS: Lazy<Mutex<DatabaseStatus>> = Lazy::new(|| {
+    Mutex::new(DatabaseStatus {
+        version: 0,
+        latest: SCHEMA_VERSION,
+        backup: None,
+        error: None,
+    })
+});
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error("База данных создана более новой версией приложения (схема {0})")]
+    TooNew(u32),
+
+    #[error("Не удалось создать резервную копию базы данных: {0}")]
+    Backup(String),
+
+    #[error("Ошибка миграции {version} ({name}): {error}")]
+    Step {
+        version: u32,
+        name: &'static str,
+        error: String,
+    },
+
+    #[error("Ошибка при исполнении Query: {0}")]
+    Query(String),
+}
+
+impl From<surrealdb::Error> for MigrationError {
+    fn from(e: surrealdb::Error) -> Self {
+        MigrationError::Query(e.to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SchemaMeta {
+    version: u32,
+}
+
+#[derive(Serialize, Clone)]
+pub struct DatabaseStatus {
+    pub version: u32,
+    pub latest: u32,
+    pub backup: Option<String>,
+    pub error: Option<String>,
+}
+
+async fn stored_version(db: &Surreal<Db>) -> Result<Option<u32>, MigrationError> {
+    let meta: Option<SchemaMeta> = db.select(("schema_meta", "current")).await?;
+    Ok(meta.map(|m| m.version))
+}
+
+async fn backup(db: &Surreal<Db>, version: u32) -> Result<PathBuf, MigrationError> {
+    let dir = MAIN_FOLDER_PREFIX.get().unwrap().join("backups");
+    std::fs::create_dir_all(&dir).map_err(|e| MigrationError::Backup(e.to_string()))?;
+    let file = dir.join(format!("db-v{}-{}.surql", version, now_millis()));
+    db.export(&file)
+        .await
+        .map_err(|e| MigrationError::Backup(e.to_string()))?;
+    Ok(file)
+}
+
+async fn run(db: &Surreal<Db>) -> Result<(), MigrationError> {
+    let stored = stored_version(db).await?;
+    let version = match stored {
+        Some(version) => version,
+        // Without a main config the database is brand new and starts at the latest schema;
+        // one created before versioning existed replays every step.
+        None => {
+            let configs = db
+                .query("SELECT count() AS total FROM main_config GROUP ALL")
+                .await?
+                .take::<Option<usize>>((0, "total"))?;
+            match configs.unwrap_or_default() {
+                0 => SCHEMA_VERSION,
+                _ => 0,
+            }
+        }
+    };
+    STATUS.lock().unwrap().version = version;
+    if version > SCHEMA_VERSION {
+        return Err(MigrationError::TooNew(version));
+    }
+    if version < SCHEMA_VERSION {
+        let file = backup(db, version).await?;
+        STATUS.lock().unwrap().backup = Some(file.to_string_lossy().to_string());
+    }
+    for (step, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        let next = step as u32 + 1;
+        // Each step commits together with its version bump.
+        db.query("BEGIN TRANSACTION")
+            .query(migration.statements)
+            .query("UPSERT schema_meta:current SET version = $version")
+            .query("COMMIT TRANSACTION")
+            .bind(("version", next))
+            .await
+            .and_then(|response| response.check())
+            .map_err(|e| MigrationError::Step {
+                version: next,
+                name: migration.name,
+                error: e.to_string(),
+            })?;
+        STATUS.lock().unwrap().version = next;
+    }
+    if stored.is_none() && version == SCHEMA_VERSION {
+        db.upsert::<Option<SchemaMeta>>(("schema_meta", "current"))
+            .content(SchemaMeta { version })
+            .await?;
+    }
+    Ok(())
+}
+
+// Brings the database to `SCHEMA_VERSION`, backing it up first when there is anything to migrate.
+pub async fn migrate(db: &Surreal<Db>) -> Result<(), MigrationError> {
+    let result = run(db).await;
+    if let Err(e) = &result {
+        STATUS.lock().unwrap().error = Some(e.to_string());
+    }
+    result
+}
+
+pub fn database_status() -> DatabaseStatus {
+    STATUS.lock().unwrap().clone()
+}
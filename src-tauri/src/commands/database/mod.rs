@@ -14,10 +14,13 @@
  * limitations under the License.
  */
 mod entities;
+mod migrations;
 
 pub use entities::*;
+pub use migrations::*;
 use once_cell::sync::OnceCell;
 use surrealdb::{Surreal, engine::local::RocksDb};
+use tauri::Emitter;
 
 use crate::MAIN_FOLDER_PREFIX;
 pub static DB: OnceCell<DbRepo> = OnceCell::new();
@@ -45,15 +48,15 @@ pub async fn init_official_plugins(app: tauri::AppHandle) -> Result<(), String>
     ];
     for plugin in official_plugins {
         let plug = vec![
-            PluginListFields::Name(plugin.to_string()),
-            PluginListFields::Author("Transhumanist".to_string()),
-            PluginListFields::Version("latest".to_string()),
+            PluginListFields::PluginName(plugin.to_string()),
+            PluginListFields::PluginAuthor("Transhumanist".to_string()),
+            PluginListFields::PluginVersion(EntityVersion::Latest),
             PluginListFields::PluginType("official".to_string()),
-            PluginListFields::PluginLink("none".to_string()),
-            PluginListFields::Installed("true".to_string()),
-            PluginListFields::Enabled("true".to_string()),
+            PluginListFields::PluginLink(None),
+            PluginListFields::IsInstalled(true),
+            PluginListFields::IsEnabled(true),
         ];
-        db.create::<PluginListFields, PluginList>(plug, app.clone(), "plugins_repo", plugin)
+        db.create::<PluginListFields, PluginList>(plug, app.clone(), PluginList::TABLE, plugin)
             .await
             .map_err(|e| e.to_string())?;
     }
@@ -63,17 +66,21 @@ pub async fn init_official_plugins(app: tauri::AppHandle) -> Result<(), String>
 #[tauri::command]
 pub async fn create_first_database(app: tauri::AppHandle) -> Result<(), String> {
     init().await;
+    if let Err(e) = migrate(&DB.get().unwrap().database).await {
+        let _ = app.emit("error", e.to_string());
+        return Err(e.to_string());
+    }
     match DB
         .get()
         .unwrap()
-        .get::<MainConfig>("singletone", "main_config")
+        .get::<MainConfig>("singletone", MainConfig::TABLE)
         .await
     {
         Ok(_) => Ok(()),
         Err(_) => {
             let input = vec![
                 MainConfigFields::Name("".to_string()),
-                MainConfigFields::FirstRun("true".to_string()),
+                MainConfigFields::FirstRun(true),
                 MainConfigFields::Workdir("".to_string()),
             ];
             DB.get()
@@ -81,7 +88,7 @@ pub async fn create_first_database(app: tauri::AppHandle) -> Result<(), String>
                 .create::<MainConfigFields, MainConfig>(
                     input,
                     app.clone(),
-                    "main_config",
+                    MainConfig::TABLE,
                     "singletone",
                 )
                 .await
@@ -90,3 +97,8 @@ pub async fn create_first_database(app: tauri::AppHandle) -> Result<(), String>
         }
     }
 }
+
+#[tauri::command]
+pub fn get_database_status() -> DatabaseStatus {
+    database_status()
+}
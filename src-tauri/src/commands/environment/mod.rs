@@ -13,19 +13,21 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use serde_json::Value;
+
 use crate::{
     MAIN_FOLDER_PREFIX,
-    commands::database::{DB, EntityError, MainConfig},
+    commands::database::{DB, Entity, EntityError, MainConfig, MainConfigFields},
 };
 
-use super::EntityControl;
+use super::{EntityControl, EntityField};
 
 #[tauri::command]
 pub async fn get_env(ename: String) -> Result<String, String> {
     match DB
         .get()
         .unwrap()
-        .get::<Option<MainConfig>>("singletone", "main_config")
+        .get::<Option<MainConfig>>("singletone", MainConfig::TABLE)
         .await
     {
         Ok(Some(data)) => match data.get_value_by_key(ename) {
@@ -39,10 +41,12 @@ pub async fn get_env(ename: String) -> Result<String, String> {
 
 #[tauri::command]
 pub async fn set_env(ename: String, name: String) -> Result<(), String> {
+    let field =
+        MainConfigFields::from_key(&ename, Value::String(name)).map_err(|e| e.to_string())?;
     match DB
         .get()
         .unwrap()
-        .update("singletone".to_string(), "main_config", ename, name)
+        .update("singletone", MainConfig::TABLE, field)
         .await
     {
         Ok(_) => Ok(()),
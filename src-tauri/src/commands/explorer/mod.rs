@@ -1,4 +1,3 @@
-use serde::Serialize;
 /**
  * Copyright 2025 The VOID Authors. All Rights Reserved.
  *
@@ -14,47 +13,224 @@ use serde::Serialize;
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::{
+    cmp::Ordering,
+    fs::Metadata,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use ignore::gitignore::Gitignore;
+
+use super::{child_path, resolve_in, setting_value, vault_ignore, workdir};
+
 #[derive(Serialize)]
 pub struct Entry {
     pub name: String,
     pub entry_type: String,
+    pub path: String,
+    pub kind: String,
+    pub size: u64,
+    pub created: Option<u64>,
+    pub modified: Option<u64>,
+    pub children: Option<usize>,
+    pub hidden: bool,
+    // Present only for folders expanded within the requested depth.
+    pub entries: Option<Vec<Entry>>,
+}
+
+#[derive(Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    #[default]
+    Name,
+    Modified,
+    Created,
+    Size,
+    Kind,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct ListOptions {
+    pub sort: SortKey,
+    pub descending: bool,
+    pub folders_first: bool,
+    pub show_hidden: bool,
+    pub depth: usize,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        ListOptions {
+            sort: SortKey::Name,
+            descending: false,
+            folders_first: true,
+            show_hidden: false,
+            depth: 0,
+        }
+    }
+}
+
+pub fn entry_kind(name: &str, is_dir: bool) -> &'static str {
+    if is_dir {
+        return "folder";
+    }
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "md" => "note",
+        "canvas" => "canvas",
+        "jpg" | "jpeg" | "png" | "webp" | "gif" | "svg" => "image",
+        "mp3" | "wav" | "ogg" | "flac" | "alac" | "opus" | "m4a" => "audio",
+        "mp4" | "mov" | "webm" | "mkv" | "avi" => "video",
+        "pdf" => "pdf",
+        _ => "other",
+    }
+}
+
+fn millis(time: std::io::Result<SystemTime>) -> Option<u64> {
+    time.ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as u64)
 }
 
 impl Entry {
-    fn new(name: String, is_dir: bool) -> Self {
-        match is_dir {
-            true => Entry {
-                name,
-                entry_type: String::from("dir"),
-            },
-            false => Entry {
-                name,
-                entry_type: String::from("file"),
-            },
+    fn new(name: String, path: String, meta: &Metadata) -> Self {
+        let is_dir = meta.is_dir();
+        Entry {
+            entry_type: String::from(if is_dir { "dir" } else { "file" }),
+            kind: entry_kind(&name, is_dir).to_string(),
+            size: if is_dir { 0 } else { meta.len() },
+            created: millis(meta.created()),
+            modified: millis(meta.modified()),
+            children: None,
+            hidden: name.starts_with('.'),
+            entries: None,
+            name,
+            path,
         }
     }
 }
 
-#[tauri::command]
-pub async fn get_directory_content(dirname: String) -> Result<Vec<Entry>, String> {
-    let mut workdir = super::get_env("workdir".to_string()).await.unwrap();
-    workdir.push('/');
-    workdir.push_str(&dirname);
-    workdir.push('/');
-    let paths = std::fs::read_dir(workdir.clone()).unwrap();
-    let dirs: Vec<Entry> = paths
-        .map(|e| {
-            let a = e.unwrap();
-            (
-                a.path().to_str().unwrap().to_string(),
-                a.metadata().unwrap().is_dir(),
-            )
+fn compare(a: &Entry, b: &Entry, options: &ListOptions) -> Ordering {
+    let folders = match options.folders_first {
+        true => (b.entry_type == "dir").cmp(&(a.entry_type == "dir")),
+        false => Ordering::Equal,
+    };
+    let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+    let order = match options.sort {
+        SortKey::Name => by_name(),
+        SortKey::Modified => a.modified.cmp(&b.modified).then_with(by_name),
+        SortKey::Created => a.created.cmp(&b.created).then_with(by_name),
+        SortKey::Size => a.size.cmp(&b.size).then_with(by_name),
+        SortKey::Kind => a.kind.cmp(&b.kind).then_with(by_name),
+    };
+    folders.then(match options.descending {
+        true => order.reverse(),
+        false => order,
+    })
+}
+
+// Deeper listings are cut off here rather than walking a whole vault in one call.
+const MAX_DEPTH: usize = 8;
+
+// The visible entries of `dir` with their absolute paths. Symlinks are left out, so a
+// listing never leads outside the vault or around a cycle; unreadable entries are skipped
+// too, only `dir` itself has to be readable.
+fn visible_entries(
+    dir: &Path,
+    relative: &str,
+    options: &ListOptions,
+    ignore: &Gitignore,
+) -> std::io::Result<Vec<(PathBuf, Entry)>> {
+    Ok(std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_str()?.to_string();
+            let meta = e.path().symlink_metadata().ok()?;
+            if meta.file_type().is_symlink() {
+                return None;
+            }
+            let path = child_path(relative, &name);
+            Some((e.path(), Entry::new(name, path, &meta)))
         })
-        .map(|d| {
-            let (name, is_dir) = d;
-            let name = name.replace(&workdir, "");
-            Entry::new(name, is_dir)
+        .filter(|(_, entry)| options.show_hidden || !entry.hidden)
+        .filter(|(_, entry)| {
+            !ignore
+                .matched_path_or_any_parents(&entry.path, entry.entry_type == "dir")
+                .is_ignore()
         })
-        .collect();
-    Ok(dirs)
+        .collect())
+}
+
+fn read_entries(
+    dir: &Path,
+    relative: &str,
+    options: &ListOptions,
+    ignore: &Gitignore,
+    depth: usize,
+) -> std::io::Result<Vec<Entry>> {
+    let mut entries = visible_entries(dir, relative, options, ignore)?
+        .into_iter()
+        .map(|(absolute, mut entry)| {
+            if entry.entry_type == "dir" {
+                match depth < options.depth.min(MAX_DEPTH) {
+                    true => {
+                        let children =
+                            read_entries(&absolute, &entry.path, options, ignore, depth + 1)
+                                .unwrap_or_default();
+                        entry.children = Some(children.len());
+                        entry.entries = Some(children);
+                    }
+                    false => {
+                        entry.children = visible_entries(&absolute, &entry.path, options, ignore)
+                            .ok()
+                            .map(|children| children.len());
+                    }
+                }
+            }
+            entry
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| compare(a, b, options));
+    Ok(entries)
+}
+
+// Options the frontend leaves out follow the explorer settings.
+async fn list_options(given: Map<String, Value>) -> Result<ListOptions, String> {
+    let mut options = Map::new();
+    for (option, key) in [
+        ("sort", "explorer.sort"),
+        ("folders_first", "explorer.folders_first"),
+        ("show_hidden", "explorer.show_hidden"),
+    ] {
+        options.insert(option.to_string(), setting_value(key).await);
+    }
+    options.extend(given);
+    serde_json::from_value(Value::Object(options)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_directory_content(
+    dirname: String,
+    options: Option<Map<String, Value>>,
+) -> Result<Vec<Entry>, String> {
+    let workdir = workdir().await?;
+    let dir = resolve_in(&workdir, &dirname)?;
+    let options = list_options(options.unwrap_or_default()).await?;
+    let ignore = vault_ignore(&workdir);
+    let (absolute, relative) = (dir.absolute, dir.relative);
+    tauri::async_runtime::spawn_blocking(move || {
+        read_entries(&absolute, &relative, &options, &ignore, 0)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("не удалось открыть папку {}: {}", dirname, e))
 }
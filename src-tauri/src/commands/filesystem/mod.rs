@@ -1,4 +1,3 @@
-use rustix::path::Arg;
 /**
  * Copyright 2025 The VOID Authors. All Rights Reserved.
  *
@@ -14,20 +13,27 @@ use rustix::path::Arg;
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use serde::{Deserialize, Serialize};
 use std::{fs, path::Path, str::FromStr};
 use tauri::Emitter;
 
 use tauri::Manager;
-use tauri_plugin_fs::FsExt;
 
-use super::get_env;
+use super::{
+    ConflictPolicy, FsAction, LinkRewrite, PathError, ProgressReporter, VaultPath, activate_vault,
+    apply_link_rewrites, child_path, copy_path, entry_moved, entry_updated, get_env, is_under,
+    move_path, move_tree, plan_link_rewrites, record_fs_op, resolve_in, resolve_readable_path,
+    resolve_vault_path, restore_trash_item, sibling_path, trash_entry, workdir,
+};
 use crate::MAIN_FOLDER_PREFIX;
 
+// Raw bytes, so large files skip JSON encoding. Reads go through here rather than the
+// fs plugin because a vault stays forbidden in its scope once another one was opened.
 #[tauri::command]
-pub fn get_file(ipath: String) -> Vec<u8> {
-    println!("{}", ipath);
-    let fpath: &Path = Path::new(&ipath);
-    fs::read(fpath).unwrap()
+pub async fn get_file(ipath: String) -> Result<tauri::ipc::Response, String> {
+    let fpath = resolve_readable_path(&ipath).await?;
+    let data = fs::read(fpath).map_err(|e| e.to_string())?;
+    Ok(tauri::ipc::Response::new(data))
 }
 
 #[tauri::command]
@@ -66,9 +72,13 @@ pub async fn get_config_directory() -> String {
 
 #[tauri::command]
 pub async fn allow_scope(app: tauri::AppHandle) {
-    let scope = app.fs_scope();
     let workdir = get_env("workdir".to_string()).await.unwrap();
-    let _ = scope.allow_directory(workdir, true);
+    if workdir.is_empty() {
+        return;
+    }
+    if let Err(e) = activate_vault(&app, &workdir).await {
+        eprintln!("Ошибка при открытии хранилища{}", e);
+    }
 }
 
 #[tauri::command]
@@ -94,51 +104,198 @@ pub fn get_all_user_fonts() -> Result<Vec<String>, String> {
         .collect::<Vec<String>>())
 }
 
+// Resolves a path inside the vault that is not the vault root itself.
+fn entry_path(workdir: &Path, path: &str) -> Result<VaultPath, String> {
+    let entry = resolve_in(workdir, path)?;
+    match entry.relative.is_empty() {
+        true => Err(PathError::OutsideVault(path.to_string()).into()),
+        false => Ok(entry),
+    }
+}
+
 #[tauri::command]
 pub async fn create_entry(name: String, path: String, flag: String) -> Result<(), String> {
-    let workdir = super::get_env("workdir".to_string()).await?;
-    let path = workdir + path.as_str() + name.as_str();
-    println!("{}", flag);
-    println!("{}", path);
-    let path = std::path::Path::new(&path);
+    let workdir = workdir().await?;
+    let entry = entry_path(&workdir, &(path + name.as_str()))?;
     match flag.as_str() {
-        "folder" => std::fs::create_dir(path).map_err(|e| e.to_string())?,
-        "file" => std::fs::write(path, "").map_err(|e| e.to_string())?,
+        "folder" => std::fs::create_dir(&entry.absolute).map_err(|e| e.to_string())?,
+        "file" => std::fs::write(&entry.absolute, "").map_err(|e| e.to_string())?,
         _ => return Err("нет такого флага".to_string()),
     }
+    entry_updated(&workdir, &entry.relative).await;
+    record_fs_op(
+        &workdir,
+        "create",
+        vec![FsAction::Trash {
+            path: entry.relative,
+        }],
+    );
     Ok(())
 }
 
+// Result of one explorer operation: notes whose links were rewritten and the steps that revert it.
+struct Applied {
+    changed: Vec<String>,
+    undo: Vec<FsAction>,
+}
+
+async fn remove_path(workdir: &Path, path: &str) -> Result<Applied, String> {
+    let entry = entry_path(workdir, path)?;
+    let item = trash_entry(workdir, &entry.relative).await?;
+    Ok(Applied {
+        changed: Vec::new(),
+        undo: vec![FsAction::Restore {
+            id: item.id,
+            path: entry.relative,
+        }],
+    })
+}
+
 #[tauri::command]
 pub async fn remove(name: String, path: String, flag: String) -> Result<(), String> {
-    let workdir = super::get_env("workdir".to_string()).await?;
-    let path = workdir + path.as_str() + name.as_str();
-    let path = std::path::Path::new(&path);
-    match flag.as_str() {
-        "folder" => std::fs::remove_dir_all(path).map_err(|e| e.to_string())?,
-        "file" => std::fs::remove_file(path).map_err(|e| e.to_string())?,
-        _ => return Err("нет такого флага".to_string()),
+    if !matches!(flag.as_str(), "folder" | "file") {
+        return Err("нет такого флага".to_string());
     }
+    let workdir = workdir().await?;
+    let applied = remove_path(&workdir, &(path + name.as_str())).await?;
+    record_fs_op(&workdir, "remove", applied.undo);
     Ok(())
 }
 
+async fn finish_move(
+    workdir: &Path,
+    from: &str,
+    to: &str,
+    rewrites: &[LinkRewrite],
+) -> Result<Vec<String>, String> {
+    let changed = apply_link_rewrites(workdir, rewrites)?;
+    entry_moved(workdir, from, to).await;
+    for path in changed.iter().filter(|p| !is_under(p, to)) {
+        entry_updated(workdir, path).await;
+    }
+    Ok(changed)
+}
+
+// Moves without replacing an existing entry and rewrites links to the moved paths.
+pub async fn move_entry(
+    workdir: &Path,
+    from: &str,
+    to: &str,
+    progress: ProgressReporter,
+) -> Result<Vec<String>, String> {
+    let (source, target) = (workdir.join(from), workdir.join(to));
+    let rewrites = plan_link_rewrites(workdir, from, to);
+    move_path(source.clone(), target.clone(), progress).await?;
+    finish_move(workdir, from, to, &rewrites)
+        .await
+        .inspect_err(|_| {
+            let _ = move_tree(&target, &source, &mut ProgressReporter::silent());
+        })
+}
+
+async fn rename_path(workdir: &Path, path: &str, new_name: &str) -> Result<Applied, String> {
+    let source = entry_path(workdir, path)?;
+    let parent = source.relative.rsplit_once('/').map_or("", |(dir, _)| dir);
+    let target = entry_path(workdir, &child_path(parent, new_name))?;
+    let (from, to) = (source.relative, target.relative);
+    let changed = move_entry(workdir, &from, &to, ProgressReporter::silent()).await?;
+    Ok(Applied {
+        changed,
+        undo: vec![FsAction::Move { from: to, to: from }],
+    })
+}
+
 #[tauri::command]
-pub async fn rename(path: String, new_name: String, app: tauri::AppHandle) -> Result<(), String> {
-    use rustix::fs::CWD;
-    use rustix::fs::{RenameFlags, renameat_with};
-    let workdir = get_env("workdir".to_string()).await.unwrap();
-    let path = std::path::PathBuf::from(workdir).join(&path);
-    let new_path = path.parent().unwrap().join(new_name);
-    println!("{:#?}:{:#?}", path, new_path);
-    let result = renameat_with(CWD, path, CWD, new_path, RenameFlags::NOREPLACE);
-    match result {
-        Ok(()) => Ok(()),
+pub async fn rename(
+    path: String,
+    new_name: String,
+    app: tauri::AppHandle,
+) -> Result<Vec<String>, String> {
+    let result = async {
+        let workdir = workdir().await?;
+        let applied = rename_path(&workdir, &path, &new_name).await?;
+        record_fs_op(&workdir, "rename", applied.undo);
+        Ok::<_, String>(applied.changed)
+    }
+    .await;
+    result.inspect_err(|response| {
+        app.emit("error", response).unwrap();
+    })
+}
+
+async fn transfer_path(
+    workdir: &Path,
+    before_path: &str,
+    after_path: &str,
+    flag: &str,
+    conflict: ConflictPolicy,
+    app: Option<&tauri::AppHandle>,
+) -> Result<Applied, String> {
+    let source = entry_path(workdir, before_path)?;
+    let folder = resolve_in(workdir, after_path)?;
+    let name = source.relative.rsplit('/').next().unwrap_or_default();
+    let target = entry_path(workdir, &child_path(&folder.relative, name))?;
+    let from = source.relative;
+    if target.relative != from && is_under(&target.relative, &from) {
+        return Err("нельзя поместить папку внутрь самой себя".to_string());
+    }
+    let skipped = Applied {
+        changed: Vec::new(),
+        undo: Vec::new(),
+    };
+    let mut undo = Vec::new();
+    let exists = target.absolute.symlink_metadata().is_ok();
+    let to = match (exists, conflict) {
+        (false, _) => target.relative,
+        (true, ConflictPolicy::Fail) => {
+            return Err(format!("`{}` уже существует", target.relative));
+        }
+        (true, ConflictPolicy::Skip) => return Ok(skipped),
+        (true, ConflictPolicy::Overwrite) if target.relative == from => return Ok(skipped),
+        (true, ConflictPolicy::Overwrite) => {
+            let item = trash_entry(workdir, &target.relative).await?;
+            undo.push(FsAction::Restore {
+                id: item.id,
+                path: target.relative.clone(),
+            });
+            target.relative
+        }
+        (true, ConflictPolicy::KeepBoth) => {
+            sibling_path(workdir, &target.relative, source.absolute.is_dir(), "copy")
+        }
+    };
+    let progress = match app {
+        Some(app) => ProgressReporter::new(app.clone(), flag, &from),
+        None => ProgressReporter::silent(),
+    };
+    let result = match flag {
+        "copy" => copy_path(source.absolute, workdir.join(&to), progress)
+            .await
+            .map(|_| (Vec::new(), FsAction::Trash { path: to.clone() })),
+        _ => move_entry(workdir, &from, &to, progress)
+            .await
+            .map(|changed| {
+                let revert = FsAction::Move {
+                    from: to.clone(),
+                    to: from.clone(),
+                };
+                (changed, revert)
+            }),
+    };
+    let (changed, revert) = match result {
+        Ok(done) => done,
         Err(e) => {
-            let response = e.to_string();
-            app.emit("error", &response).unwrap();
-            Err(response)
+            if let Some(FsAction::Restore { id, .. }) = undo.first() {
+                let _ = restore_trash_item(workdir, id).await;
+            }
+            return Err(e);
         }
+    };
+    if flag == "copy" {
+        entry_updated(workdir, &to).await;
     }
+    undo.insert(0, revert);
+    Ok(Applied { changed, undo })
 }
 
 #[tauri::command]
@@ -146,33 +303,229 @@ pub async fn modify_entry(
     before_path: String,
     after_path: String,
     flag: String,
+    conflict: Option<ConflictPolicy>,
     app: tauri::AppHandle,
-) -> Result<(), String> {
-    let workdir = std::path::PathBuf::from(get_env("workdir".to_string()).await.unwrap());
-    let before_path = workdir.join(before_path.as_str());
-    let after_path = workdir
-        .join(after_path.as_str())
-        .join(before_path.file_name().unwrap());
-    match flag.as_str() {
-        "copy" => {
-            std::fs::copy(before_path, after_path).map_err(|e| {
-                app.emit("error", e.to_string()).unwrap();
-                e.to_string()
-            })?;
-        }
-        "move" => {
-            std::fs::rename(before_path, after_path).map_err(|e| {
-                app.emit("error", e.to_string()).unwrap();
-                e.to_string()
-            })?;
+) -> Result<Vec<String>, String> {
+    if !matches!(flag.as_str(), "copy" | "move") {
+        return Ok(Vec::new());
+    }
+    let result = async {
+        let workdir = workdir().await?;
+        let conflict = conflict.unwrap_or_default();
+        let applied = transfer_path(
+            &workdir,
+            &before_path,
+            &after_path,
+            &flag,
+            conflict,
+            Some(&app),
+        )
+        .await?;
+        record_fs_op(&workdir, &flag, applied.undo);
+        Ok::<_, String>(applied.changed)
+    }
+    .await;
+    result.inspect_err(|response| {
+        app.emit("error", response).unwrap();
+    })
+}
+
+#[tauri::command]
+pub async fn get_absolute_path(subpath: String) -> Result<String, String> {
+    let entry = resolve_vault_path(&subpath).await?;
+    Ok(entry.absolute.to_string_lossy().into_owned())
+}
+
+#[derive(Deserialize)]
+pub struct RenameRequest {
+    pub path: String,
+    pub new_name: String,
+}
+
+#[derive(Serialize)]
+pub struct BatchItem {
+    pub path: String,
+    pub ok: bool,
+    pub error: Option<String>,
+    pub changed: Vec<String>,
+}
+
+#[derive(Serialize, Default)]
+pub struct BatchReport {
+    pub items: Vec<BatchItem>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+// Collects per-item outcomes; the whole batch is journaled as a single undo step.
+#[derive(Default)]
+struct Batch {
+    report: BatchReport,
+    undo: Vec<Vec<FsAction>>,
+}
+
+impl Batch {
+    fn push(&mut self, path: String, result: Result<Applied, String>) {
+        let item = match result {
+            Ok(applied) => {
+                self.undo.push(applied.undo);
+                BatchItem {
+                    path,
+                    ok: true,
+                    error: None,
+                    changed: applied.changed,
+                }
+            }
+            Err(e) => BatchItem {
+                path,
+                ok: false,
+                error: Some(e),
+                changed: Vec::new(),
+            },
+        };
+        match item.ok {
+            true => self.report.succeeded += 1,
+            false => self.report.failed += 1,
         }
-        _ => (),
+        self.report.items.push(item);
     }
-    Ok(())
+
+    fn finish(self, workdir: &Path, label: &str) -> BatchReport {
+        let undo = self.undo.into_iter().rev().flatten().collect();
+        record_fs_op(workdir, label, undo);
+        self.report
+    }
+}
+
+// Entries inside another selected folder travel with it and need no separate step.
+fn covered_by_selection(workdir: &Path, paths: &[String], path: &str) -> bool {
+    let relative = |p: &str| resolve_in(workdir, p).ok().map(|e| e.relative);
+    let Some(own) = relative(path).filter(|r| !r.is_empty()) else {
+        return false;
+    };
+    paths
+        .iter()
+        .filter_map(|p| relative(p))
+        .any(|other| !other.is_empty() && other != own && is_under(&own, &other))
+}
+
+#[tauri::command]
+pub async fn remove_entries(paths: Vec<String>) -> Result<BatchReport, String> {
+    let workdir = workdir().await?;
+    let mut batch = Batch::default();
+    for path in &paths {
+        let result = match covered_by_selection(&workdir, &paths, path) {
+            true => Ok(Applied {
+                changed: Vec::new(),
+                undo: Vec::new(),
+            }),
+            false => remove_path(&workdir, path).await,
+        };
+        batch.push(path.clone(), result);
+    }
+    Ok(batch.finish(&workdir, "remove"))
+}
+
+#[tauri::command]
+pub async fn rename_entries(entries: Vec<RenameRequest>) -> Result<BatchReport, String> {
+    let workdir = workdir().await?;
+    let mut batch = Batch::default();
+    for entry in entries {
+        let result = rename_path(&workdir, &entry.path, &entry.new_name).await;
+        batch.push(entry.path, result);
+    }
+    Ok(batch.finish(&workdir, "rename"))
 }
 
 #[tauri::command]
-pub async fn get_absolute_path(subpath: String) -> String {
-    let workdir = std::path::PathBuf::from(get_env("workdir".to_string()).await.unwrap());
-    workdir.join(subpath).as_str().unwrap().to_string()
+pub async fn modify_entries(
+    before_paths: Vec<String>,
+    after_path: String,
+    flag: String,
+    conflict: Option<ConflictPolicy>,
+    app: tauri::AppHandle,
+) -> Result<BatchReport, String> {
+    if !matches!(flag.as_str(), "copy" | "move") {
+        return Err("нет такого флага".to_string());
+    }
+    let workdir = workdir().await?;
+    let conflict = conflict.unwrap_or_default();
+    let mut batch = Batch::default();
+    for path in &before_paths {
+        let result = match covered_by_selection(&workdir, &before_paths, path) {
+            true => Ok(Applied {
+                changed: Vec::new(),
+                undo: Vec::new(),
+            }),
+            false => transfer_path(&workdir, path, &after_path, &flag, conflict, Some(&app)).await,
+        };
+        batch.push(path.clone(), result);
+    }
+    Ok(batch.finish(&workdir, &flag))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn vault(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("void-fs-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn rename_keeps_root_entries_at_the_root() {
+        let root = vault("rename");
+        fs::write(root.join("a.md"), "# A").unwrap();
+        fs::write(root.join("b.md"), "See [[a]]").unwrap();
+        let applied = tauri::async_runtime::block_on(rename_path(&root, "a.md", "c.md")).unwrap();
+        assert!(root.join("c.md").exists() && !root.join("a.md").exists());
+        assert_eq!(applied.changed, ["b.md"]);
+        assert_eq!(fs::read_to_string(root.join("b.md")).unwrap(), "See [[c]]");
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    fn transfer(root: &Path, path: &str, folder: &str, flag: &str, conflict: ConflictPolicy) {
+        tauri::async_runtime::block_on(transfer_path(root, path, folder, flag, conflict, None))
+            .unwrap();
+    }
+
+    #[test]
+    fn transfer_moves_into_the_vault_root() {
+        let root = vault("to-root");
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::write(root.join("notes/a.md"), "# A").unwrap();
+        transfer(&root, "notes/a.md", "", "move", ConflictPolicy::Fail);
+        assert!(root.join("a.md").exists() && !root.join("notes/a.md").exists());
+        transfer(&root, "a.md", "/notes", "copy", ConflictPolicy::Fail);
+        assert!(root.join("a.md").exists() && root.join("notes/a.md").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn transfer_follows_the_conflict_policy() {
+        let root = vault("conflict");
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::write(root.join("a.md"), "root").unwrap();
+        fs::write(root.join("notes/a.md"), "notes").unwrap();
+        let failed = tauri::async_runtime::block_on(transfer_path(
+            &root,
+            "notes/a.md",
+            "",
+            "move",
+            ConflictPolicy::Fail,
+            None,
+        ));
+        assert!(failed.is_err());
+        transfer(&root, "notes/a.md", "", "copy", ConflictPolicy::Skip);
+        assert_eq!(fs::read_to_string(root.join("a.md")).unwrap(), "root");
+        transfer(&root, "notes/a.md", "", "copy", ConflictPolicy::KeepBoth);
+        let copy = fs::read_to_string(root.join("a (copy).md")).unwrap();
+        assert_eq!(copy, "notes");
+        let _ = fs::remove_dir_all(&root);
+    }
 }
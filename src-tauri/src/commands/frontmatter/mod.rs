@@ -0,0 +1,200 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use super::{VaultPath, entry_updated, resolve_in, resolve_vault_path, workdir, write_atomic};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum PropertyValue {
+    Text(String),
+    Number(f64),
+    Date(String),
+    List(Vec<String>),
+    Link(String),
+    Checkbox(bool),
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct NoteProperty {
+    pub key: String,
+    pub value: PropertyValue,
+}
+
+pub fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if matches!(line.trim_end(), "---" | "...") {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+fn is_date(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    if bytes.len() < 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    let number = |range: std::ops::Range<usize>| text.get(range)?.parse::<u32>().ok();
+    let (Some(_), Some(month), Some(day)) = (number(0..4), number(5..7), number(8..10)) else {
+        return false;
+    };
+    (1..=12).contains(&month)
+        && (1..=31).contains(&day)
+        && (bytes.len() == 10 || matches!(bytes[10], b'T' | b' '))
+}
+
+fn link_target(text: &str) -> Option<String> {
+    let inner = text.trim().strip_prefix("[[")?.strip_suffix("]]")?;
+    Some(inner.to_string())
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Sequence(items) if items.len() == 1 => match &items[0] {
+            Value::String(s) => format!("[[{}]]", s),
+            other => value_text(other),
+        },
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+fn typed_value(value: &Value) -> PropertyValue {
+    match value {
+        Value::Bool(b) => PropertyValue::Checkbox(*b),
+        Value::Number(n) => PropertyValue::Number(n.as_f64().unwrap_or_default()),
+        Value::String(s) => match link_target(s) {
+            Some(target) => PropertyValue::Link(target),
+            None if is_date(s) => PropertyValue::Date(s.clone()),
+            None => PropertyValue::Text(s.clone()),
+        },
+        Value::Sequence(items) => match items.as_slice() {
+            [Value::Sequence(inner)] if matches!(inner.as_slice(), [Value::String(_)]) => {
+                PropertyValue::Link(value_text(&inner[0]))
+            }
+            _ => PropertyValue::List(items.iter().map(value_text).collect()),
+        },
+        other => PropertyValue::Text(value_text(other)),
+    }
+}
+
+fn yaml_value(value: PropertyValue) -> Value {
+    match value {
+        PropertyValue::Text(s) | PropertyValue::Date(s) => Value::String(s),
+        PropertyValue::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+            Value::Number((n as i64).into())
+        }
+        PropertyValue::Number(n) => Value::Number(n.into()),
+        PropertyValue::List(items) => {
+            Value::Sequence(items.into_iter().map(Value::String).collect())
+        }
+        PropertyValue::Link(target) => Value::String(format!("[[{}]]", target)),
+        PropertyValue::Checkbox(b) => Value::Bool(b),
+    }
+}
+
+fn parse_mapping(content: &str) -> Result<Mapping, String> {
+    match split_frontmatter(content).0 {
+        Some(header) if !header.trim().is_empty() => {
+            serde_yaml::from_str::<Mapping>(header).map_err(|e| e.to_string())
+        }
+        _ => Ok(Mapping::new()),
+    }
+}
+
+fn properties(mapping: &Mapping) -> Vec<NoteProperty> {
+    mapping
+        .iter()
+        .map(|(key, value)| NoteProperty {
+            key: value_text(key),
+            value: typed_value(value),
+        })
+        .collect()
+}
+
+pub fn parse_properties(content: &str) -> Vec<NoteProperty> {
+    properties(&parse_mapping(content).unwrap_or_default())
+}
+
+fn with_mapping(content: &str, mapping: &Mapping) -> Result<String, String> {
+    let body = split_frontmatter(content).1;
+    if mapping.is_empty() {
+        return Ok(body.to_string());
+    }
+    let header = serde_yaml::to_string(mapping).map_err(|e| e.to_string())?;
+    Ok(format!("---\n{}---\n{}", header, body))
+}
+
+async fn edit_properties<F>(path: String, edit: F) -> Result<Vec<NoteProperty>, String>
+where
+    F: FnOnce(&mut Mapping),
+{
+    let workdir = workdir().await?;
+    let VaultPath {
+        absolute: file,
+        relative,
+    } = resolve_in(&workdir, &path)?;
+    let content = std::fs::read_to_string(&file).map_err(|e| e.to_string())?;
+    let mut mapping = parse_mapping(&content)?;
+    edit(&mut mapping);
+    let content = with_mapping(&content, &mapping)?;
+    write_atomic(&file, content.as_bytes()).map_err(|e| e.to_string())?;
+    entry_updated(&workdir, &relative).await;
+    Ok(properties(&mapping))
+}
+
+#[tauri::command]
+pub async fn get_note_properties(path: String) -> Result<Vec<NoteProperty>, String> {
+    let note = resolve_vault_path(&path).await?;
+    let content = std::fs::read_to_string(&note.absolute).map_err(|e| e.to_string())?;
+    Ok(properties(&parse_mapping(&content)?))
+}
+
+#[tauri::command]
+pub async fn set_note_property(
+    path: String,
+    key: String,
+    value: PropertyValue,
+) -> Result<Vec<NoteProperty>, String> {
+    edit_properties(path, |mapping| {
+        mapping.insert(Value::String(key), yaml_value(value));
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn remove_note_property(path: String, key: String) -> Result<Vec<NoteProperty>, String> {
+    edit_properties(path, |mapping| {
+        mapping.shift_remove(key.as_str());
+    })
+    .await
+}
@@ -0,0 +1,396 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::{DB, LinkGraph, entry_link_graph, is_under, normalize_path, vault_link_graph, workdir};
+
+static GRAPH_ROOT: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+static GRAPH_DIRTY: AtomicBool = AtomicBool::new(true);
+
+const CLEAR_QUERY: &str = "
+    DEFINE INDEX IF NOT EXISTS vault_node_path ON vault_node FIELDS path UNIQUE;
+    BEGIN TRANSACTION;
+    DELETE links_to;
+    DELETE tagged;
+    DELETE vault_node;
+    DELETE vault_tag;
+";
+
+// Drops what a change under `$path` may have altered: the nodes there and the edges
+// leaving `$sources`.
+const CLEAR_ENTRY_QUERY: &str = "
+    BEGIN TRANSACTION;
+    FOR $source IN $sources {
+        LET $from = type::thing('vault_node', $source);
+        DELETE links_to WHERE in = $from;
+        DELETE tagged WHERE in = $from;
+    };
+    DELETE vault_node WHERE path = $path OR string::starts_with(path, $prefix);
+";
+
+const INSERT_QUERY: &str = "
+    FOR $node IN $nodes {
+        CREATE type::thing('vault_node', $node.path) CONTENT $node;
+    };
+    FOR $link IN $links {
+        LET $from = type::thing('vault_node', $link.source);
+        LET $to = type::thing('vault_node', $link.target);
+        RELATE $from->links_to->$to SET embed = $link.embed;
+    };
+    FOR $tag IN $tags {
+        LET $note = type::thing('vault_node', $tag.source);
+        LET $label = type::thing('vault_tag', $tag.target);
+        UPSERT $label SET name = $tag.target;
+        RELATE $note->tagged->$label;
+    };
+    DELETE vault_tag WHERE array::len(<-tagged) = 0;
+    COMMIT TRANSACTION;
+";
+
+// Notes with an edge into anything under `$path`, read before the change is written.
+const LINKING_QUERY: &str = "
+    SELECT VALUE <-links_to<-vault_node.path FROM vault_node
+    WHERE path = $path OR string::starts_with(path, $prefix)
+";
+
+// Links count in either direction, as in the focus neighbourhood.
+const SHORTEST_PATH_QUERY: &str = "
+    LET $from = type::thing('vault_node', $from_path);
+    LET $to = type::thing('vault_node', $to_path);
+    RETURN ($from.{..+shortest=$to}<->links_to<->vault_node).path;
+";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphNode {
+    pub path: String,
+    pub name: String,
+    pub kind: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: String,
+}
+
+#[derive(Serialize)]
+pub struct VaultGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct GraphFilters {
+    pub folder: Option<String>,
+    pub tag: Option<String>,
+    pub focus: Option<String>,
+    pub depth: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct LinkRow {
+    source: String,
+    target: String,
+    embed: bool,
+}
+
+#[derive(Serialize)]
+struct TagRow {
+    source: String,
+    target: String,
+}
+
+#[derive(Deserialize)]
+struct EdgeRow {
+    source: String,
+    target: String,
+    embed: Option<bool>,
+}
+
+fn node_kind(path: &str) -> &'static str {
+    if path.ends_with(".md") {
+        "note"
+    } else if path.ends_with(".canvas") {
+        "canvas"
+    } else {
+        "attachment"
+    }
+}
+
+fn tag_id(tag: &str) -> String {
+    format!("#{}", tag)
+}
+
+pub fn mark_graph_dirty() {
+    GRAPH_DIRTY.store(true, Ordering::SeqCst);
+}
+
+async fn write_graph(
+    clear: &str,
+    path: &str,
+    sources: Vec<String>,
+    graph: LinkGraph,
+) -> Result<(), String> {
+    let nodes = graph
+        .files
+        .iter()
+        .map(|path| GraphNode {
+            path: path.clone(),
+            name: path.rsplit('/').next().unwrap_or(path).to_string(),
+            kind: node_kind(path).to_string(),
+        })
+        .collect::<Vec<_>>();
+    let links = graph
+        .links
+        .into_iter()
+        .filter_map(|l| {
+            Some(LinkRow {
+                target: l.target?,
+                source: l.source,
+                embed: l.embed,
+            })
+        })
+        .collect::<Vec<_>>();
+    let tags = graph
+        .tags
+        .into_iter()
+        .map(|(source, target)| TagRow { source, target })
+        .collect::<Vec<_>>();
+    DB.get()
+        .unwrap()
+        .database
+        .query(clear)
+        .query(INSERT_QUERY)
+        .bind(("path", path.to_string()))
+        .bind(("prefix", format!("{}/", path)))
+        .bind(("sources", sources))
+        .bind(("nodes", nodes))
+        .bind(("links", links))
+        .bind(("tags", tags))
+        .await
+        .map_err(|e| e.to_string())?
+        .check()
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+async fn ensure_graph(root: &Path) -> Result<(), String> {
+    let mut synced = GRAPH_ROOT.lock().await;
+    if synced.as_deref() == Some(root) && !GRAPH_DIRTY.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
+    GRAPH_DIRTY.store(false, Ordering::SeqCst);
+    write_graph(CLEAR_QUERY, "", Vec::new(), vault_link_graph(root)).await?;
+    *synced = Some(root.to_path_buf());
+    Ok(())
+}
+
+async fn linking_into(path: &str) -> Result<BTreeSet<String>, String> {
+    let rows = DB
+        .get()
+        .unwrap()
+        .database
+        .query(LINKING_QUERY)
+        .bind(("path", path.to_string()))
+        .bind(("prefix", format!("{}/", path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .take::<Vec<Vec<String>>>(0)
+        .map_err(|e| e.to_string())?;
+    Ok(rows.into_iter().flatten().collect())
+}
+
+async fn write_entry(root: &Path, path: &str) -> Result<(), String> {
+    let before = linking_into(path).await?;
+    let Some((sources, graph)) = entry_link_graph(root, path, before) else {
+        return Err("индекс ссылок не готов".to_string());
+    };
+    let sources = sources.into_iter().collect();
+    write_graph(CLEAR_ENTRY_QUERY, path, sources, graph).await
+}
+
+// Called once the link index has taken the change in, so only the nodes under `path`
+// and the edges of notes linking to or from them are rewritten. A graph that was never
+// built or is about to be rebuilt is left alone.
+pub async fn update_graph_entry(root: &Path, path: &str) {
+    let synced = GRAPH_ROOT.lock().await;
+    if synced.as_deref() != Some(root) || GRAPH_DIRTY.load(Ordering::SeqCst) {
+        return;
+    }
+    if let Err(e) = write_entry(root, path).await {
+        eprintln!("Не удалось обновить граф для {}: {}", path, e);
+        mark_graph_dirty();
+    }
+}
+
+async fn neighbourhood(focus: String, depth: usize) -> Result<HashSet<String>, String> {
+    if depth == 0 {
+        return Ok(HashSet::from([focus]));
+    }
+    // Recursion bounds are part of the syntax and cannot be bound; SurrealDB stops at 256.
+    let query = format!(
+        "LET $focus = type::thing('vault_node', $path);
+        RETURN ($focus.{{1..{}+collect}}<->links_to<->vault_node).path;",
+        depth.min(256)
+    );
+    let mut around = DB
+        .get()
+        .unwrap()
+        .database
+        .query(query)
+        .bind(("path", focus.clone()))
+        .await
+        .map_err(|e| e.to_string())?
+        .take::<Option<Vec<String>>>(1)
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default()
+        .into_iter()
+        .collect::<HashSet<_>>();
+    around.insert(focus);
+    Ok(around)
+}
+
+#[tauri::command]
+pub async fn get_vault_graph(filters: Option<GraphFilters>) -> Result<VaultGraph, String> {
+    let workdir = workdir().await?;
+    ensure_graph(&workdir).await?;
+    let filters = filters.unwrap_or_default();
+    let mut response = DB
+        .get()
+        .unwrap()
+        .database
+        .query("SELECT path, name, kind FROM vault_node")
+        .query("SELECT in.path AS source, out.path AS target, embed FROM links_to")
+        .query("SELECT in.path AS source, out.name AS target FROM tagged")
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut nodes = response
+        .take::<Vec<GraphNode>>(0)
+        .map_err(|e| e.to_string())?;
+    let links = response
+        .take::<Vec<EdgeRow>>(1)
+        .map_err(|e| e.to_string())?;
+    let tags = response
+        .take::<Vec<EdgeRow>>(2)
+        .map_err(|e| e.to_string())?;
+
+    if let Some(folder) = filters.folder.map(|f| normalize_path(&f)) {
+        nodes.retain(|n| is_under(&n.path, &folder));
+    }
+    if let Some(tag) = filters.tag {
+        let tag = tag.trim_start_matches('#');
+        let tagged = tags
+            .iter()
+            .filter(|t| t.target == tag)
+            .map(|t| t.source.as_str())
+            .collect::<HashSet<_>>();
+        nodes.retain(|n| tagged.contains(n.path.as_str()));
+    }
+    if let Some(focus) = filters.focus.map(|f| normalize_path(&f)) {
+        let around = neighbourhood(focus, filters.depth.unwrap_or(1)).await?;
+        nodes.retain(|n| around.contains(&n.path));
+    }
+
+    let kept = nodes.iter().map(|n| n.path.clone()).collect::<HashSet<_>>();
+    let mut edges = links
+        .into_iter()
+        .filter(|l| kept.contains(&l.source) && kept.contains(&l.target))
+        .map(|l| GraphEdge {
+            kind: match l.embed.unwrap_or_default() {
+                true => "embed".to_string(),
+                false => "link".to_string(),
+            },
+            source: l.source,
+            target: l.target,
+        })
+        .collect::<Vec<_>>();
+    let mut tag_nodes = HashMap::new();
+    for tag in tags.into_iter().filter(|t| kept.contains(&t.source)) {
+        tag_nodes
+            .entry(tag.target.clone())
+            .or_insert_with(|| GraphNode {
+                path: tag_id(&tag.target),
+                name: tag.target.clone(),
+                kind: "tag".to_string(),
+            });
+        edges.push(GraphEdge {
+            source: tag.source,
+            target: tag_id(&tag.target),
+            kind: "tag".to_string(),
+        });
+    }
+    nodes.extend(tag_nodes.into_values());
+    Ok(VaultGraph { nodes, edges })
+}
+
+#[tauri::command]
+pub async fn get_shortest_path(from: String, to: String) -> Result<Option<Vec<String>>, String> {
+    let workdir = workdir().await?;
+    ensure_graph(&workdir).await?;
+    let (from, to) = (normalize_path(&from), normalize_path(&to));
+    if from == to {
+        return Ok(Some(vec![from]));
+    }
+    let steps = DB
+        .get()
+        .unwrap()
+        .database
+        .query(SHORTEST_PATH_QUERY)
+        .bind(("from_path", from.clone()))
+        .bind(("to_path", to.clone()))
+        .await
+        .map_err(|e| e.to_string())?
+        .take::<Option<Vec<String>>>(2)
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    if steps.last() != Some(&to) {
+        return Ok(None);
+    }
+    let mut path = steps;
+    if path.first() != Some(&from) {
+        path.insert(0, from);
+    }
+    Ok(Some(path))
+}
+
+#[tauri::command]
+pub async fn get_orphan_notes() -> Result<Vec<String>, String> {
+    let workdir = workdir().await?;
+    ensure_graph(&workdir).await?;
+    let mut orphans = DB
+        .get()
+        .unwrap()
+        .database
+        .query(
+            "SELECT VALUE path FROM vault_node
+            WHERE kind = 'note' AND array::len(->links_to) = 0 AND array::len(<-links_to) = 0",
+        )
+        .await
+        .map_err(|e| e.to_string())?
+        .take::<Vec<String>>(0)
+        .map_err(|e| e.to_string())?;
+    orphans.sort();
+    Ok(orphans)
+}
@@ -0,0 +1,280 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use git2::{
+    Index, IndexEntry, IndexTime, MergeFileOptions, Odb, Patch, Repository, Signature, Sort,
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use super::{entry_updated, resolve_in, workdir, write_atomic};
+use crate::MAIN_FOLDER_PREFIX;
+
+const HISTORY_DELAY: Duration = Duration::from_secs(5);
+
+static PENDING: Lazy<Mutex<HashMap<String, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize)]
+pub struct NoteVersion {
+    pub rev: String,
+    pub time: i64,
+    pub message: String,
+}
+
+fn history_dir(workdir: &Path) -> PathBuf {
+    MAIN_FOLDER_PREFIX
+        .get()
+        .unwrap()
+        .join("history")
+        .join(urlencoding::encode(&workdir.to_string_lossy()).into_owned())
+}
+
+fn open_history(workdir: &Path) -> Result<Repository, git2::Error> {
+    let dir = history_dir(workdir);
+    Repository::open_bare(&dir).or_else(|_| Repository::init_bare(&dir))
+}
+
+fn history_enabled(workdir: &Path) -> bool {
+    Repository::open_bare(history_dir(workdir))
+        .and_then(|repo| repo.config()?.get_bool("void.enabled"))
+        .unwrap_or(false)
+}
+
+fn record_version(
+    workdir: &Path,
+    path: &str,
+    message: &str,
+) -> Result<Option<String>, git2::Error> {
+    let repo = open_history(workdir)?;
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let mut index = Index::new()?;
+    if let Some(parent) = &parent {
+        index.read_tree(&parent.tree()?)?;
+    }
+    match std::fs::read(workdir.join(path)) {
+        Ok(data) => {
+            index.add(&blob_entry(&repo, path, &data)?)?;
+        }
+        Err(_) => {
+            let _ = index.remove_path(Path::new(path));
+        }
+    }
+    let tree_id = index.write_tree_to(&repo)?;
+    if parent.as_ref().is_some_and(|p| p.tree_id() == tree_id) {
+        return Ok(None);
+    }
+    let tree = repo.find_tree(tree_id)?;
+    let signature = Signature::now("VOID", "void@localhost")?;
+    let parents = parent.iter().collect::<Vec<_>>();
+    let oid = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?;
+    Ok(Some(oid.to_string()))
+}
+
+fn note_versions(workdir: &Path, path: &str) -> Result<Vec<NoteVersion>, git2::Error> {
+    // Reading never creates the history repo, only enabling history does.
+    let Ok(repo) = Repository::open_bare(history_dir(workdir)) else {
+        return Ok(Vec::new());
+    };
+    if repo.head().is_err() {
+        return Ok(Vec::new());
+    }
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+    walk.set_sorting(Sort::TIME)?;
+    let mut versions = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let current = commit
+            .tree()?
+            .get_path(Path::new(path))
+            .ok()
+            .map(|e| e.id());
+        let previous = match commit.parent(0) {
+            Ok(parent) => parent
+                .tree()?
+                .get_path(Path::new(path))
+                .ok()
+                .map(|e| e.id()),
+            Err(_) => None,
+        };
+        if current.is_some() && current != previous {
+            versions.push(NoteVersion {
+                rev: commit.id().to_string(),
+                time: commit.time().seconds(),
+                message: commit.message().unwrap_or_default().to_string(),
+            });
+        }
+    }
+    Ok(versions)
+}
+
+fn note_at_version(workdir: &Path, path: &str, rev: &str) -> Result<Vec<u8>, git2::Error> {
+    let repo = Repository::open_bare(history_dir(workdir))?;
+    let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+    let entry = commit.tree()?.get_path(Path::new(path))?;
+    let blob = repo.find_blob(entry.id())?;
+    Ok(blob.content().to_vec())
+}
+
+fn blob_entry(repo: &Repository, path: &str, data: &[u8]) -> Result<IndexEntry, git2::Error> {
+    Ok(IndexEntry {
+        ctime: IndexTime::new(0, 0),
+        mtime: IndexTime::new(0, 0),
+        dev: 0,
+        ino: 0,
+        mode: 0o100644,
+        uid: 0,
+        gid: 0,
+        file_size: data.len() as u32,
+        id: repo.blob(data)?,
+        flags: 0,
+        flags_extended: 0,
+        path: path.as_bytes().to_vec(),
+    })
+}
+
+// Blobs for a merge only need to live as long as the merge, so they go to an
+// in-memory object database instead of the history repo, which may be disabled.
+fn scratch_repo() -> Result<Repository, git2::Error> {
+    let odb = Odb::new()?;
+    odb.add_new_mempack_backend(1)?;
+    Repository::from_odb(odb)
+}
+
+pub fn merge_note_text(
+    path: &str,
+    base: &str,
+    ours: &str,
+    theirs: &str,
+) -> Result<Option<String>, git2::Error> {
+    let repo = scratch_repo()?;
+    let mut options = MergeFileOptions::new();
+    options.our_label("editor").their_label("disk");
+    let result = repo.merge_file_from_index(
+        &blob_entry(&repo, path, base.as_bytes())?,
+        &blob_entry(&repo, path, ours.as_bytes())?,
+        &blob_entry(&repo, path, theirs.as_bytes())?,
+        Some(&mut options),
+    )?;
+    match result.is_automergeable() {
+        true => Ok(Some(String::from_utf8_lossy(result.content()).into_owned())),
+        false => Ok(None),
+    }
+}
+
+fn unified_diff(path: &str, old: &[u8], new: &[u8]) -> Result<String, git2::Error> {
+    let path = Path::new(path);
+    let mut patch = Patch::from_buffers(old, Some(path), new, Some(path), None)?;
+    let buf = patch.to_buf()?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+pub fn schedule_note_version(workdir: PathBuf, path: String) {
+    if !history_enabled(&workdir) {
+        return;
+    }
+    let generation = {
+        let mut pending = PENDING.lock().unwrap();
+        let generation = pending.entry(path.clone()).or_default();
+        *generation += 1;
+        *generation
+    };
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(HISTORY_DELAY).await;
+        {
+            let mut pending = PENDING.lock().unwrap();
+            if pending.get(&path) != Some(&generation) {
+                return;
+            }
+            pending.remove(&path);
+        }
+        if let Err(e) = record_version(&workdir, &path, &format!("Update {}", path)) {
+            eprintln!("Ошибка при сохранении версии{}", e);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_note_history_enabled() -> Result<bool, String> {
+    Ok(history_enabled(&workdir().await?))
+}
+
+#[tauri::command]
+pub async fn set_note_history_enabled(enabled: bool) -> Result<(), String> {
+    let workdir = workdir().await?;
+    if !enabled && !history_dir(&workdir).exists() {
+        return Ok(());
+    }
+    open_history(&workdir)
+        .and_then(|repo| repo.config()?.set_bool("void.enabled", enabled))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_note_versions(path: String) -> Result<Vec<NoteVersion>, String> {
+    let workdir = workdir().await?;
+    note_versions(&workdir, &resolve_in(&workdir, &path)?.relative).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_note_at_version(path: String, rev: String) -> Result<String, String> {
+    let workdir = workdir().await?;
+    let path = resolve_in(&workdir, &path)?.relative;
+    let content = note_at_version(&workdir, &path, &rev).map_err(|e| e.to_string())?;
+    String::from_utf8(content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn diff_note_versions(
+    path: String,
+    from: String,
+    to: Option<String>,
+) -> Result<String, String> {
+    let workdir = workdir().await?;
+    let path = resolve_in(&workdir, &path)?.relative;
+    let old = note_at_version(&workdir, &path, &from).map_err(|e| e.to_string())?;
+    let new = match to {
+        Some(rev) => note_at_version(&workdir, &path, &rev).map_err(|e| e.to_string())?,
+        None => std::fs::read(workdir.join(&path)).map_err(|e| e.to_string())?,
+    };
+    unified_diff(&path, &old, &new).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn restore_note_version(path: String, rev: String) -> Result<(), String> {
+    let workdir = workdir().await?;
+    let path = resolve_in(&workdir, &path)?.relative;
+    let content = note_at_version(&workdir, &path, &rev).map_err(|e| e.to_string())?;
+    write_atomic(&workdir.join(&path), &content).map_err(|e| e.to_string())?;
+    entry_updated(&workdir, &path).await;
+    let short = rev.get(..7).unwrap_or(&rev);
+    record_version(&workdir, &path, &format!("Restore {} to {}", path, short))
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
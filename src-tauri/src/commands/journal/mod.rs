@@ -0,0 +1,240 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::Emitter;
+
+use super::{
+    ProgressReporter, entry_removed, entry_updated, move_entry, restore_trash_item, trash_entry,
+    workdir, write_atomic,
+};
+
+const JOURNAL_LIMIT: usize = 100;
+
+static JOURNAL: Lazy<Mutex<Journal>> = Lazy::new(|| Mutex::new(Journal::default()));
+
+// A single step that can be replayed; running one yields the step that reverts it.
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum FsAction {
+    Trash {
+        path: String,
+    },
+    Restore {
+        id: String,
+        path: String,
+    },
+    Move {
+        from: String,
+        to: String,
+    },
+    Write {
+        path: String,
+        content: Option<String>,
+    },
+}
+
+struct JournalEntry {
+    label: String,
+    actions: Vec<FsAction>,
+}
+
+#[derive(Default)]
+struct Journal {
+    root: Option<PathBuf>,
+    undo: Vec<JournalEntry>,
+    redo: Vec<JournalEntry>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct FsOpReport {
+    pub label: String,
+    pub paths: Vec<String>,
+}
+
+impl Journal {
+    fn reset_for(&mut self, root: &Path) {
+        if self.root.as_deref() != Some(root) {
+            *self = Journal {
+                root: Some(root.to_path_buf()),
+                ..Default::default()
+            };
+        }
+    }
+}
+
+impl FsAction {
+    fn paths(&self) -> Vec<String> {
+        match self {
+            FsAction::Trash { path }
+            | FsAction::Restore { path, .. }
+            | FsAction::Write { path, .. } => vec![path.clone()],
+            FsAction::Move { from, to } => vec![from.clone(), to.clone()],
+        }
+    }
+}
+
+fn push_entry(stack: &mut Vec<JournalEntry>, entry: JournalEntry) {
+    stack.push(entry);
+    if stack.len() > JOURNAL_LIMIT {
+        stack.remove(0);
+    }
+}
+
+// `inverse` lists the actions that revert the operation, in the order they must run.
+pub fn record_fs_op(workdir: &Path, label: &str, inverse: Vec<FsAction>) {
+    if inverse.is_empty() {
+        return;
+    }
+    let mut journal = JOURNAL.lock().unwrap();
+    journal.reset_for(workdir);
+    journal.redo.clear();
+    push_entry(
+        &mut journal.undo,
+        JournalEntry {
+            label: label.to_string(),
+            actions: inverse,
+        },
+    );
+}
+
+async fn run_action(workdir: &Path, action: FsAction) -> Result<FsAction, String> {
+    match action {
+        FsAction::Trash { path } => {
+            let item = trash_entry(workdir, &path).await?;
+            Ok(FsAction::Restore { id: item.id, path })
+        }
+        FsAction::Restore { id, .. } => {
+            let path = restore_trash_item(workdir, &id).await?;
+            Ok(FsAction::Trash { path })
+        }
+        FsAction::Move { from, to } => {
+            move_entry(workdir, &from, &to, ProgressReporter::silent()).await?;
+            Ok(FsAction::Move { from: to, to: from })
+        }
+        FsAction::Write { path, content } => {
+            let file = workdir.join(&path);
+            let previous = std::fs::read_to_string(&file).ok();
+            match &content {
+                Some(content) => {
+                    write_atomic(&file, content.as_bytes()).map_err(|e| e.to_string())?;
+                    entry_updated(workdir, &path).await;
+                }
+                None => {
+                    std::fs::remove_file(&file).map_err(|e| e.to_string())?;
+                    entry_removed(workdir, &path).await;
+                }
+            }
+            Ok(FsAction::Write {
+                path,
+                content: previous,
+            })
+        }
+    }
+}
+
+async fn replay(app: &tauri::AppHandle, undo: bool) -> Result<Option<FsOpReport>, String> {
+    let workdir = workdir().await?;
+    let entry = {
+        let mut journal = JOURNAL.lock().unwrap();
+        journal.reset_for(&workdir);
+        match undo {
+            true => journal.undo.pop(),
+            false => journal.redo.pop(),
+        }
+    };
+    let Some(entry) = entry else {
+        return Ok(None);
+    };
+    let mut paths = Vec::new();
+    let mut reverted = Vec::new();
+    let mut actions = entry.actions.into_iter();
+    let mut failure = None;
+    for action in actions.by_ref() {
+        paths.extend(action.paths());
+        match run_action(&workdir, action.clone()).await {
+            Ok(inverse) => reverted.push(inverse),
+            Err(e) => {
+                failure = Some((action, e));
+                break;
+            }
+        }
+    }
+    reverted.reverse();
+    {
+        let mut journal = JOURNAL.lock().unwrap();
+        // Whatever ran can be replayed the other way; whatever did not stays where it was.
+        if let Some((action, _)) = &failure {
+            let remaining = std::iter::once(action.clone())
+                .chain(actions)
+                .collect::<Vec<_>>();
+            let stack = match undo {
+                true => &mut journal.undo,
+                false => &mut journal.redo,
+            };
+            push_entry(
+                stack,
+                JournalEntry {
+                    label: entry.label.clone(),
+                    actions: remaining,
+                },
+            );
+        }
+        if !reverted.is_empty() {
+            let stack = match undo {
+                true => &mut journal.redo,
+                false => &mut journal.undo,
+            };
+            push_entry(
+                stack,
+                JournalEntry {
+                    label: entry.label.clone(),
+                    actions: reverted,
+                },
+            );
+        }
+    }
+    if let Some((_, e)) = failure {
+        app.emit("error", &e).unwrap();
+        return Err(e);
+    }
+    paths.dedup();
+    let report = FsOpReport {
+        label: entry.label,
+        paths,
+    };
+    let event = match undo {
+        true => "fs-op-undone",
+        false => "fs-op-redone",
+    };
+    app.emit(event, &report).unwrap();
+    Ok(Some(report))
+}
+
+#[tauri::command]
+pub async fn undo_last_fs_op(app: tauri::AppHandle) -> Result<Option<FsOpReport>, String> {
+    replay(&app, true).await
+}
+
+#[tauri::command]
+pub async fn redo_fs_op(app: tauri::AppHandle) -> Result<Option<FsOpReport>, String> {
+    replay(&app, false).await
+}
@@ -0,0 +1,799 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    PropertyValue, is_under, normalize_path, parse_properties, relative_path, walk_files, workdir,
+    write_atomic,
+};
+
+static LINK_INDEX: Lazy<RwLock<LinkIndex>> = Lazy::new(|| RwLock::new(LinkIndex::default()));
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Wiki,
+    Markdown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Link {
+    pub source: String,
+    pub target: Option<String>,
+    pub raw: String,
+    pub anchor: Option<String>,
+    pub alias: Option<String>,
+    pub kind: LinkKind,
+    pub embed: bool,
+    pub line: usize,
+    pub span: (usize, usize),
+}
+
+#[derive(Default)]
+struct LinkIndex {
+    root: Option<PathBuf>,
+    files: BTreeSet<String>,
+    names: HashMap<String, Vec<String>>,
+    outgoing: HashMap<String, Vec<Link>>,
+    tags: HashMap<String, Vec<String>>,
+}
+
+pub struct LinkRewrite {
+    pub path: String,
+    pub original: String,
+    pub content: String,
+}
+
+pub struct LinkGraph {
+    pub files: Vec<String>,
+    pub links: Vec<Link>,
+    pub tags: Vec<(String, String)>,
+}
+
+impl LinkIndex {
+    fn ensure(&mut self, root: &Path) {
+        if self.root.as_deref() == Some(root) {
+            return;
+        }
+        *self = LinkIndex {
+            root: Some(root.to_path_buf()),
+            ..Default::default()
+        };
+        self.insert_entry(root, "");
+    }
+
+    fn insert_entry(&mut self, root: &Path, path: &str) {
+        for file in walk_files(root, path) {
+            if let Some(relative) = relative_path(root, &file) {
+                self.insert_file(root, relative);
+            }
+        }
+    }
+
+    fn insert_file(&mut self, root: &Path, path: String) {
+        if self.files.insert(path.clone()) {
+            self.names
+                .entry(name_key(file_name(&path)))
+                .or_default()
+                .push(path.clone());
+        }
+        if is_linkable(&path) {
+            match std::fs::read_to_string(root.join(&path)) {
+                Ok(content) => {
+                    self.tags.insert(path.clone(), parse_tags(&content));
+                    self.outgoing
+                        .insert(path.clone(), file_links(&path, &content));
+                }
+                Err(_) => {
+                    self.tags.remove(&path);
+                    self.outgoing.remove(&path);
+                }
+            }
+        }
+    }
+
+    fn remove_entry(&mut self, path: &str) {
+        let removed = self
+            .files
+            .iter()
+            .filter(|file| is_under(file, path))
+            .cloned()
+            .collect::<Vec<_>>();
+        for file in removed {
+            self.files.remove(&file);
+            self.outgoing.remove(&file);
+            self.tags.remove(&file);
+            let key = name_key(file_name(&file));
+            if let Some(paths) = self.names.get_mut(&key) {
+                paths.retain(|p| p != &file);
+                if paths.is_empty() {
+                    self.names.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn resolve(&self, link: &Link) -> Option<String> {
+        match link.kind {
+            LinkKind::Markdown => {
+                let base = match link.raw.starts_with('/') {
+                    true => "",
+                    false => parent(&link.source),
+                };
+                self.find_file(&join_relative(base, &link.raw)?)
+            }
+            LinkKind::Wiki if link.raw.contains('/') => self
+                .find_file(&normalize_path(&link.raw))
+                .or_else(|| self.find_file(&join_relative(parent(&link.source), &link.raw)?)),
+            LinkKind::Wiki => {
+                let candidates = self.names.get(&name_key(&link.raw))?;
+                candidates
+                    .iter()
+                    .find(|c| parent(c) == parent(&link.source))
+                    .or_else(|| {
+                        candidates
+                            .iter()
+                            .min_by_key(|c| (c.matches('/').count(), c.len()))
+                    })
+                    .cloned()
+            }
+        }
+    }
+
+    fn find_file(&self, path: &str) -> Option<String> {
+        if self.files.contains(path) {
+            return Some(path.to_string());
+        }
+        let with_extension = format!("{}.md", path);
+        self.files
+            .contains(&with_extension)
+            .then_some(with_extension)
+    }
+
+    fn resolved(&self, link: &Link) -> Link {
+        Link {
+            target: self.resolve(link),
+            ..link.clone()
+        }
+    }
+
+    fn outgoing(&self, path: &str) -> Vec<Link> {
+        self.outgoing
+            .get(path)
+            .map(|links| links.iter().map(|l| self.resolved(l)).collect())
+            .unwrap_or_default()
+    }
+
+    fn backlinks(&self, path: &str) -> Vec<Link> {
+        let mut links = self
+            .outgoing
+            .values()
+            .flatten()
+            .map(|l| self.resolved(l))
+            .filter(|l| l.target.as_deref() == Some(path))
+            .collect::<Vec<_>>();
+        links.sort_by(|a, b| a.source.cmp(&b.source).then(a.line.cmp(&b.line)));
+        links
+    }
+}
+
+fn parse_tags(content: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut in_fence = false;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let mut in_code = false;
+        let mut previous = ' ';
+        for (i, c) in line.char_indices() {
+            if c == '`' {
+                in_code = !in_code;
+            } else if c == '#' && !in_code && previous.is_whitespace() {
+                let tag = line[i + 1..]
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '/'))
+                    .collect::<String>();
+                let tag = tag.trim_end_matches('/');
+                if tag.chars().any(|c| !c.is_ascii_digit()) && !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+            }
+            previous = c;
+        }
+    }
+    for property in parse_properties(content) {
+        let values = match (property.key.as_str(), property.value) {
+            ("tags", PropertyValue::List(values)) => values,
+            ("tags", PropertyValue::Text(value)) => {
+                value.split([',', ' ']).map(String::from).collect()
+            }
+            _ => continue,
+        };
+        for tag in values {
+            let tag = tag.trim().trim_start_matches('#');
+            if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+    tags
+}
+
+fn is_linkable(path: &str) -> bool {
+    path.ends_with(".md") || path.ends_with(".canvas")
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+fn name_key(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.strip_suffix(".md") {
+        Some(stem) => stem.to_string(),
+        None => name,
+    }
+}
+
+fn join_relative(base: &str, path: &str) -> Option<String> {
+    let mut parts = base
+        .split('/')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>();
+    for part in path.split('/') {
+        match part {
+            "" | "." => (),
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+fn parse_links(source: &str, content: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut in_fence = false;
+    for (number, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence {
+            let offset = offset_in(content, line);
+            for mut link in parse_line(source, line, number + 1) {
+                link.span = (link.span.0 + offset, link.span.1 + offset);
+                links.push(link);
+            }
+        }
+    }
+    links
+}
+
+fn is_canvas(path: &str) -> bool {
+    path.ends_with(".canvas")
+}
+
+fn visit_strings(value: &mut Value, visit: &mut dyn FnMut(&mut String)) {
+    match value {
+        Value::String(text) => visit(text),
+        Value::Array(items) => items.iter_mut().for_each(|item| visit_strings(item, visit)),
+        Value::Object(map) => map.values_mut().for_each(|item| visit_strings(item, visit)),
+        _ => (),
+    }
+}
+
+// Canvases are JSON, so their links are read from the string values rather than the
+// raw text; spans and lines are then relative to the string holding the link.
+fn file_links(source: &str, content: &str) -> Vec<Link> {
+    if !is_canvas(source) {
+        return parse_links(source, content);
+    }
+    let Ok(mut value) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    let mut links = Vec::new();
+    visit_strings(&mut value, &mut |text| {
+        links.extend(parse_links(source, text))
+    });
+    links
+}
+
+fn rewrite_text(
+    source: &str,
+    text: &str,
+    rewrite: &dyn Fn(&Link, &str) -> Option<String>,
+) -> Option<String> {
+    let edits = parse_links(source, text)
+        .into_iter()
+        .filter_map(|link| {
+            let written = &text[link.span.0..link.span.1];
+            let new = rewrite(&link, written)?;
+            (new != written).then_some((link.span, new))
+        })
+        .collect::<Vec<_>>();
+    (!edits.is_empty()).then(|| apply_edits(text, edits))
+}
+
+// New content of `source` with `rewrite` applied to each of its links, if anything changed.
+// Canvas strings are edited as values and the document is serialized again, so the new
+// text is escaped properly.
+fn rewrite_file_links(
+    source: &str,
+    content: &str,
+    rewrite: &dyn Fn(&Link, &str) -> Option<String>,
+) -> Option<String> {
+    if !is_canvas(source) {
+        return rewrite_text(source, content, rewrite);
+    }
+    let mut value = serde_json::from_str::<Value>(content).ok()?;
+    let mut changed = false;
+    visit_strings(&mut value, &mut |text| {
+        if let Some(new) = rewrite_text(source, text, rewrite) {
+            *text = new;
+            changed = true;
+        }
+    });
+    match changed {
+        true => serde_json::to_string_pretty(&value).ok(),
+        false => None,
+    }
+}
+
+fn offset_in(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+fn parse_line(source: &str, line: &str, number: usize) -> Vec<Link> {
+    let mut links = Vec::new();
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'`' {
+            match line[i + 1..].find('`') {
+                Some(end) => i += end + 2,
+                None => i += 1,
+            }
+            continue;
+        }
+        let embed = bytes[i] == b'!' && bytes.get(i + 1) == Some(&b'[');
+        let start = if embed { i + 1 } else { i };
+        if bytes[start] != b'[' {
+            i += 1;
+            continue;
+        }
+        if bytes.get(start + 1) == Some(&b'[') {
+            if let Some(end) = line[start + 2..].find("]]") {
+                let inner = &line[start + 2..start + 2 + end];
+                links.extend(wiki_link(source, line, inner, embed, number));
+                i = start + end + 4;
+                continue;
+            }
+        } else if let Some(destination) = markdown_destination(line, start) {
+            links.extend(markdown_link(source, line, destination, embed, number));
+        }
+        i = start + 1;
+    }
+    links
+}
+
+fn wiki_link(source: &str, text: &str, inner: &str, embed: bool, line: usize) -> Option<Link> {
+    let (target, alias) = match inner.split_once('|') {
+        Some((target, alias)) => (target, Some(alias.trim().to_string())),
+        None => (inner, None),
+    };
+    let (raw, anchor) = match target.split_once('#') {
+        Some((raw, anchor)) => (raw.trim(), Some(anchor.trim().to_string())),
+        None => (target.trim(), None),
+    };
+    if raw.is_empty() {
+        return None;
+    }
+    let start = offset_in(text, raw);
+    Some(Link {
+        source: source.to_string(),
+        target: None,
+        raw: raw.to_string(),
+        anchor,
+        alias,
+        kind: LinkKind::Wiki,
+        embed,
+        line,
+        span: (start, start + raw.len()),
+    })
+}
+
+fn markdown_destination(line: &str, start: usize) -> Option<&str> {
+    let bytes = line.as_bytes();
+    let mut depth = 0;
+    let mut close = None;
+    for (i, byte) in bytes.iter().enumerate().skip(start) {
+        match byte {
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(i);
+                    break;
+                }
+            }
+            _ => (),
+        }
+    }
+    let open = close? + 1;
+    if bytes.get(open) != Some(&b'(') {
+        return None;
+    }
+    let mut depth = 0;
+    for (i, byte) in bytes.iter().enumerate().skip(open) {
+        match byte {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&line[open + 1..i]);
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+fn markdown_link(
+    source: &str,
+    text: &str,
+    destination: &str,
+    embed: bool,
+    line: usize,
+) -> Option<Link> {
+    let destination = destination.trim();
+    let destination = match destination.strip_prefix('<') {
+        Some(rest) => rest.split('>').next()?,
+        None => destination.split_whitespace().next()?,
+    };
+    if destination.contains("://")
+        || destination.starts_with('#')
+        || destination.starts_with("mailto:")
+        || destination.starts_with("data:")
+    {
+        return None;
+    }
+    let (raw, anchor) = match destination.split_once('#') {
+        Some((raw, anchor)) => (raw, Some(anchor.to_string())),
+        None => (destination, None),
+    };
+    let raw = raw.split('?').next().unwrap_or(raw);
+    let start = offset_in(text, raw);
+    let span = (start, start + raw.len());
+    let raw = urlencoding::decode(raw)
+        .map(|r| r.into_owned())
+        .unwrap_or_else(|_| raw.to_string());
+    if raw.is_empty() {
+        return None;
+    }
+    Some(Link {
+        source: source.to_string(),
+        target: None,
+        raw,
+        anchor,
+        alias: None,
+        kind: LinkKind::Markdown,
+        embed,
+        line,
+        span,
+    })
+}
+
+fn relative_between(dir: &str, target: &str) -> String {
+    let dir = dir.split('/').filter(|p| !p.is_empty()).collect::<Vec<_>>();
+    let target = target.split('/').collect::<Vec<_>>();
+    let common = dir
+        .iter()
+        .zip(target.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut parts = vec![".."; dir.len() - common];
+    parts.extend(&target[common..]);
+    parts.join("/")
+}
+
+fn rewritten_target(link: &Link, written: &str, source: &str, old: &str, target: &str) -> String {
+    let target = match link.raw.to_lowercase().ends_with(".md") {
+        true => target,
+        false => target.strip_suffix(".md").unwrap_or(target),
+    };
+    match link.kind {
+        LinkKind::Wiki if link.raw.contains('/') => target.to_string(),
+        LinkKind::Wiki if name_key(file_name(old)) == name_key(file_name(target)) => {
+            written.to_string()
+        }
+        LinkKind::Wiki => file_name(target).to_string(),
+        LinkKind::Markdown => {
+            let path = match link.raw.starts_with('/') {
+                true => format!("/{}", target),
+                false => relative_between(parent(source), target),
+            };
+            match written == link.raw && !path.contains(char::is_whitespace) {
+                true => path,
+                false => path
+                    .split('/')
+                    .map(|part| urlencoding::encode(part).into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            }
+        }
+    }
+}
+
+pub fn plan_link_rewrites(root: &Path, from: &str, to: &str) -> Vec<LinkRewrite> {
+    if from.is_empty() || from == to {
+        return Vec::new();
+    }
+    let mut index = LINK_INDEX.write().unwrap();
+    index.ensure(root);
+    let relocate = |path: &str| match is_under(path, from) {
+        true => format!("{}{}", to, &path[from.len()..]),
+        false => path.to_string(),
+    };
+    let sources = index
+        .outgoing
+        .iter()
+        .filter(|(source, links)| {
+            is_under(source, from)
+                || links
+                    .iter()
+                    .any(|l| index.resolve(l).is_some_and(|t| is_under(&t, from)))
+        })
+        .map(|(source, _)| source.clone())
+        .collect::<BTreeSet<_>>();
+    let mut rewrites = Vec::new();
+    for source in sources {
+        let Ok(original) = std::fs::read_to_string(root.join(&source)) else {
+            continue;
+        };
+        let new_source = relocate(&source);
+        let content = rewrite_file_links(&source, &original, &|link, written| {
+            let target = index.resolve(link)?;
+            if !is_under(&source, from) && !is_under(&target, from) {
+                return None;
+            }
+            Some(rewritten_target(
+                link,
+                written,
+                &new_source,
+                &target,
+                &relocate(&target),
+            ))
+        });
+        let Some(content) = content else {
+            continue;
+        };
+        rewrites.push(LinkRewrite {
+            path: new_source,
+            content,
+            original,
+        });
+    }
+    rewrites
+}
+
+fn apply_edits(original: &str, mut edits: Vec<((usize, usize), String)>) -> String {
+    edits.sort_by(|a, b| b.0.0.cmp(&a.0.0));
+    let mut content = original.to_string();
+    for ((start, end), text) in edits {
+        content.replace_range(start..end, &text);
+    }
+    content
+}
+
+pub fn incoming_links(root: &Path, path: &str) -> Vec<Link> {
+    let mut index = LINK_INDEX.write().unwrap();
+    index.ensure(root);
+    let mut links = index
+        .outgoing
+        .iter()
+        .filter(|(source, _)| !is_under(source, path))
+        .flat_map(|(_, links)| links.iter().map(|l| index.resolved(l)))
+        .filter(|l| l.target.as_deref().is_some_and(|t| is_under(t, path)))
+        .collect::<Vec<_>>();
+    links.sort_by(|a, b| a.source.cmp(&b.source).then(a.line.cmp(&b.line)));
+    links
+}
+
+// Points links recorded before `from` went away at its new location `to`.
+pub fn plan_restored_links(root: &Path, links: &[Link], from: &str, to: &str) -> Vec<LinkRewrite> {
+    if from == to {
+        return Vec::new();
+    }
+    let relocate = |path: &str| format!("{}{}", to, &path[from.len()..]);
+    let sources = links
+        .iter()
+        .map(|l| l.source.as_str())
+        .collect::<BTreeSet<_>>();
+    let mut rewrites = Vec::new();
+    for source in sources {
+        let Ok(original) = std::fs::read_to_string(root.join(source)) else {
+            continue;
+        };
+        // A link is resolved from its text and source alone, so the same text in the same
+        // note pointed at the same target even if the note was edited since.
+        let content = rewrite_file_links(source, &original, &|link, written| {
+            let known = links
+                .iter()
+                .find(|l| l.source == source && l.raw == link.raw)?;
+            let target = known.target.as_deref().filter(|t| is_under(t, from))?;
+            Some(rewritten_target(
+                link,
+                written,
+                source,
+                target,
+                &relocate(target),
+            ))
+        });
+        let Some(content) = content else {
+            continue;
+        };
+        rewrites.push(LinkRewrite {
+            path: source.to_string(),
+            content,
+            original,
+        });
+    }
+    rewrites
+}
+
+pub fn apply_link_rewrites(root: &Path, rewrites: &[LinkRewrite]) -> Result<Vec<String>, String> {
+    let mut written: Vec<&LinkRewrite> = Vec::new();
+    for rewrite in rewrites {
+        if let Err(e) = write_atomic(&root.join(&rewrite.path), rewrite.content.as_bytes()) {
+            for done in written {
+                let _ = write_atomic(&root.join(&done.path), done.original.as_bytes());
+            }
+            return Err(e.to_string());
+        }
+        written.push(rewrite);
+    }
+    Ok(written.into_iter().map(|r| r.path.clone()).collect())
+}
+
+pub fn index_entry_links(root: &Path, path: &str) {
+    let mut index = LINK_INDEX.write().unwrap();
+    if index.root.as_deref() != Some(root) {
+        return;
+    }
+    index.remove_entry(path);
+    index.insert_entry(root, path);
+}
+
+pub fn reset_link_index() {
+    LINK_INDEX.write().unwrap().root = None;
+}
+
+pub fn forget_entry_links(root: &Path, path: &str) {
+    let mut index = LINK_INDEX.write().unwrap();
+    if index.root.as_deref() == Some(root) {
+        index.remove_entry(path);
+    }
+}
+
+pub fn vault_link_graph(root: &Path) -> LinkGraph {
+    let mut index = LINK_INDEX.write().unwrap();
+    index.ensure(root);
+    LinkGraph {
+        files: index.files.iter().cloned().collect(),
+        links: index
+            .outgoing
+            .values()
+            .flatten()
+            .map(|l| index.resolved(l))
+            .filter(|l| l.target.is_some())
+            .collect(),
+        tags: index
+            .tags
+            .iter()
+            .flat_map(|(path, tags)| tags.iter().map(|t| (path.clone(), t.clone())))
+            .collect(),
+    }
+}
+
+// The notes whose graph edges change with `path`: those under it, those linking into it
+// and the given `sources`, with the files now under `path` and the notes' links and tags.
+// `None` while the index belongs to another vault.
+pub fn entry_link_graph(
+    root: &Path,
+    path: &str,
+    mut sources: BTreeSet<String>,
+) -> Option<(BTreeSet<String>, LinkGraph)> {
+    let index = LINK_INDEX.read().unwrap();
+    if index.root.as_deref() != Some(root) {
+        return None;
+    }
+    for (source, links) in &index.outgoing {
+        let linked = links
+            .iter()
+            .any(|l| index.resolve(l).is_some_and(|t| is_under(&t, path)));
+        if linked || is_under(source, path) {
+            sources.insert(source.clone());
+        }
+    }
+    let graph = LinkGraph {
+        files: index
+            .files
+            .iter()
+            .filter(|f| is_under(f, path))
+            .cloned()
+            .collect(),
+        links: sources
+            .iter()
+            .flat_map(|s| index.outgoing(s))
+            .filter(|l| l.target.is_some())
+            .collect(),
+        tags: sources
+            .iter()
+            .flat_map(|s| {
+                let tags = index.tags.get(s).into_iter().flatten();
+                tags.map(move |t| (s.clone(), t.clone()))
+            })
+            .collect(),
+    };
+    Some((sources, graph))
+}
+
+#[tauri::command]
+pub async fn get_outgoing_links(path: String) -> Result<Vec<Link>, String> {
+    let workdir = workdir().await?;
+    let mut index = LINK_INDEX.write().unwrap();
+    index.ensure(&workdir);
+    Ok(index.outgoing(&normalize_path(&path)))
+}
+
+#[tauri::command]
+pub async fn get_backlinks(path: String) -> Result<Vec<Link>, String> {
+    let workdir = workdir().await?;
+    let mut index = LINK_INDEX.write().unwrap();
+    index.ensure(&workdir);
+    Ok(index.backlinks(&normalize_path(&path)))
+}
+
+#[tauri::command]
+pub async fn rebuild_link_index() -> Result<(), String> {
+    let workdir = workdir().await?;
+    let mut index = LINK_INDEX.write().unwrap();
+    index.root = None;
+    index.ensure(&workdir);
+    Ok(())
+}
@@ -1,5 +1,3 @@
-use crate::commands::get_env;
-
 /**
  * Copyright 2025 The VOID Authors. All Rights Reserved.
  *
@@ -15,18 +13,119 @@ use crate::commands::get_env;
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::{collections::HashMap, sync::Mutex};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::commands::{
+    VaultPath, entry_updated, merge_note_text, resolve_in, resolve_vault_path,
+    schedule_note_version, workdir, write_atomic,
+};
+
+// Recent contents handed out per note, newest last. A save is merged against the
+// one its token was issued for, since other windows may have read the note since.
+static BASES: Lazy<Mutex<HashMap<String, Vec<String>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+const BASES_PER_NOTE: usize = 8;
+
+#[derive(Serialize)]
+pub struct NoteContent {
+    content: String,
+    token: String,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum WriteResult {
+    Written { token: String },
+    Merged { token: String, content: String },
+    Conflict { token: String, content: String },
+}
+
+fn content_token(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+fn remember_base(path: &str, content: &str) -> String {
+    let mut bases = BASES.lock().unwrap();
+    let bases = bases.entry(path.to_string()).or_default();
+    bases.retain(|base| base != content);
+    bases.push(content.to_string());
+    if bases.len() > BASES_PER_NOTE {
+        bases.remove(0);
+    }
+    content_token(content)
+}
+
+fn find_base(path: &str, token: &str) -> Option<String> {
+    BASES
+        .lock()
+        .unwrap()
+        .get(path)?
+        .iter()
+        .find(|base| content_token(base) == token)
+        .cloned()
+}
+
 #[tauri::command]
-pub async fn get_note_content(path: String) -> Result<String, String> {
-    let workdir = std::path::PathBuf::from(get_env("workdir".to_string()).await.unwrap());
-    let path = workdir.join(&path);
-    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
-    Ok(content)
+pub async fn get_note_content(path: String) -> Result<NoteContent, String> {
+    let note = resolve_vault_path(&path).await?;
+    let relative = note.relative;
+    let content = std::fs::read_to_string(&note.absolute).map_err(|e| e.to_string())?;
+    let token = remember_base(&relative, &content);
+    Ok(NoteContent { content, token })
 }
 
 #[tauri::command]
-pub async fn write_note_changes(path: String, value: String) -> Result<(), String> {
-    let workdir = std::path::PathBuf::from(get_env("workdir".to_string()).await.unwrap());
-    let path = workdir.join(&path);
-    std::fs::write(path, value).map_err(|e| e.to_string())?;
-    Ok(())
+pub async fn write_note_changes(
+    path: String,
+    value: String,
+    token: Option<String>,
+) -> Result<WriteResult, String> {
+    let workdir = workdir().await?;
+    let VaultPath {
+        absolute: path,
+        relative,
+    } = resolve_in(&workdir, &path)?;
+    let disk = std::fs::read_to_string(&path).ok();
+    let stale = match (&token, &disk) {
+        (Some(token), Some(disk)) => *token != content_token(disk),
+        _ => false,
+    };
+    let (value, merged) = match (stale, disk) {
+        (true, Some(disk)) => {
+            // Without the exact base the client started from a merge could undo
+            // changes made since, so a forgotten token is a conflict.
+            let base = token.and_then(|token| find_base(&relative, &token));
+            let merged = base.and_then(|base| {
+                merge_note_text(&relative, &base, &value, &disk)
+                    .ok()
+                    .flatten()
+            });
+            match merged {
+                Some(merged) => (merged, true),
+                None => {
+                    let token = remember_base(&relative, &disk);
+                    return Ok(WriteResult::Conflict {
+                        token,
+                        content: disk,
+                    });
+                }
+            }
+        }
+        _ => (value, false),
+    };
+    write_atomic(&path, value.as_bytes()).map_err(|e| e.to_string())?;
+    let token = remember_base(&relative, &value);
+    entry_updated(&workdir, &relative).await;
+    schedule_note_version(workdir, relative);
+    Ok(match merged {
+        true => WriteResult::Merged {
+            token,
+            content: value,
+        },
+        false => WriteResult::Written { token },
+    })
 }
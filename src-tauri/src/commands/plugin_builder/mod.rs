@@ -13,12 +13,11 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use super::get_env;
+use super::resolve_vault_path;
 
 #[tauri::command]
-pub async fn read_plugin(name: String) -> String {
-    let workspace = get_env("workdir".to_string()).await.unwrap();
-    let path = format!("{}/.conf/plugins/{}/dist/plugin.js", workspace, name);
-    let path = std::path::Path::new(&path);
-    std::fs::read_to_string(path).unwrap()
+pub async fn read_plugin(name: String) -> Result<String, String> {
+    let path = format!(".conf/plugins/{}/dist/plugin.js", name);
+    let path = resolve_vault_path(&path).await?;
+    std::fs::read_to_string(path.absolute).map_err(|e| e.to_string())
 }
@@ -15,10 +15,14 @@
  */
 use serde::Deserialize;
 use tauri::Emitter;
+use url::Url;
 
 use crate::MAIN_FOLDER_PREFIX;
 
-use super::{DB, EntityControl, PluginList, PluginListFields, add_repo};
+use super::{
+    DB, Entity, EntityVersion, ListQuery, PluginList, PluginListFields, add_repo,
+    apply_vault_plugins, deserialize_link, now_millis, parse_link,
+};
 
 #[derive(Deserialize)]
 struct PluginManifest {
@@ -34,6 +38,13 @@ struct PluginManifestMember {
     plugin_type: String,
 }
 
+#[derive(Deserialize)]
+struct PluginSource {
+    plugin_name: String,
+    #[serde(default, deserialize_with = "deserialize_link")]
+    plugin_link: Option<Url>,
+}
+
 #[tauri::command]
 pub async fn create_plugins_table(url: String, app: tauri::AppHandle) -> Result<(), String> {
     let db = DB.get().unwrap();
@@ -56,19 +67,20 @@ pub async fn create_plugins_table(url: String, app: tauri::AppHandle) -> Result<
         }
     };
     let object = serde_json::from_str::<PluginManifest>(&manifest).unwrap();
+    let link = parse_link(&url).map_err(|e| e.to_string())?;
     let item = vec![
-        PluginListFields::Name(object.member.name.clone()),
-        PluginListFields::Author(object.member.author),
-        PluginListFields::Version(object.member.version),
+        PluginListFields::PluginName(object.member.name.clone()),
+        PluginListFields::PluginAuthor(object.member.author),
+        PluginListFields::PluginVersion(EntityVersion::from(object.member.version.as_str())),
         PluginListFields::PluginType(object.member.plugin_type),
-        PluginListFields::PluginLink(url.clone()),
-        PluginListFields::Installed("false".to_string()),
-        PluginListFields::Enabled("false".to_string()),
+        PluginListFields::PluginLink(link),
+        PluginListFields::IsInstalled(false),
+        PluginListFields::IsEnabled(false),
     ];
     db.create::<PluginListFields, PluginList>(
         item,
         app.clone(),
-        "plugins_repo",
+        PluginList::TABLE,
         object.member.name.as_str(),
     )
     .await
@@ -77,68 +89,63 @@ pub async fn create_plugins_table(url: String, app: tauri::AppHandle) -> Result<
 }
 
 #[tauri::command]
-pub async fn get_list_of_plugins(key: String) -> Result<Vec<PluginList>, String> {
-    let db = DB.get().unwrap();
-    let result = match key.as_str() {
-        "installed" => db
-            .get_all_members::<PluginList>("plugins_repo")
-            .await
-            .map_err(|e| e.to_string())?
-            .into_iter()
-            .filter(|e| {
-                e.get_value_by_key("installed".to_string())
-                    .unwrap()
-                    .as_str()
-                    != "false"
-            })
-            .collect::<Vec<_>>(),
-        "not_installed" => db
-            .get_all_members::<PluginList>("plugins_repo")
-            .await
-            .map_err(|e| e.to_string())?
-            .into_iter()
-            .filter(|p| {
-                p.get_value_by_key("installed".to_string())
-                    .unwrap()
-                    .as_str()
-                    == "false"
-            })
-            .collect::<Vec<_>>(),
-        _ => Vec::<PluginList>::new(),
+pub async fn get_list_of_plugins(
+    key: String,
+    query: Option<ListQuery>,
+) -> Result<Vec<PluginList>, String> {
+    let installed = match key.as_str() {
+        "installed" => true,
+        "not_installed" => false,
+        _ => return Ok(Vec::new()),
     };
-    Ok(result)
+    let mut plugins = DB
+        .get()
+        .unwrap()
+        .query::<PluginList>()
+        .filter(PluginListFields::IsInstalled(installed))
+        .apply(query.unwrap_or_default())
+        .fetch()
+        .await
+        .map_err(|e| e.to_string())?;
+    apply_vault_plugins(&mut plugins).await;
+    Ok(plugins)
 }
 #[tauri::command]
 pub async fn clone_plugin(key: String) -> Result<(), String> {
     let db = DB.get().unwrap();
     let plugin = db
-        .get::<PluginList>(key.as_str(), "plugins_repo")
+        .get::<PluginList>(key.as_str(), PluginList::TABLE)
         .await
         .unwrap();
     let plugin_dir = MAIN_FOLDER_PREFIX.get().unwrap().join("plugins");
+    let link = plugin.plugin_link.clone().ok_or(format!(
+        "У плагина `{}` нет репозитория",
+        plugin.plugin_name
+    ))?;
 
-    let _ = git2::Repository::clone(
-        format!(
-            "https://{}.git",
-            plugin.get_value_by_key("link".to_string()).unwrap()
-        )
-        .as_str(),
-        plugin_dir,
-    )
-    .map_err(|e| e.to_string())?;
+    let _ = git2::Repository::clone(format!("{}.git", link).as_str(), plugin_dir)
+        .map_err(|e| e.to_string())?;
+    // Links stored before they were typed have no scheme, so they are compared after parsing.
     let extensions = db
-        .get_all_members::<PluginList>("plugins_repo")
+        .query::<PluginList>()
+        .select(&["plugin_name", "plugin_link"])
+        .fetch_as::<PluginSource>()
         .await
         .map_err(|e| e.to_string())?;
+    let installed_at = now_millis();
     for ext in extensions {
-        if ext.get_value_by_key("link".to_string()).unwrap()
-            == plugin.get_value_by_key("link".to_string()).unwrap()
-        {
+        if ext.plugin_link.as_ref() == Some(&link) {
             db.update(
-                ext.get_value_by_key("plugin_name".to_string()).unwrap(),
-                "plugins_repo",
-                "is_installed".to_string(),
-                "true".to_string(),
+                &ext.plugin_name,
+                PluginList::TABLE,
+                PluginListFields::IsInstalled(true),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            db.update(
+                &ext.plugin_name,
+                PluginList::TABLE,
+                PluginListFields::InstalledAt(Some(installed_at)),
             )
             .await
             .map_err(|e| e.to_string())?;
@@ -148,9 +155,13 @@ pub async fn clone_plugin(key: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn operate_plugin(plug_name: String, val: String) -> Result<(), String> {
+pub async fn operate_plugin(plug_name: String, val: bool) -> Result<(), String> {
     let db = DB.get().unwrap();
-    db.update(plug_name, "plugins_repo", "is_enabled".to_string(), val)
-        .await
-        .map_err(|e| e.to_string())
+    db.update(
+        &plug_name,
+        PluginList::TABLE,
+        PluginListFields::IsEnabled(val),
+    )
+    .await
+    .map_err(|e| e.to_string())
 }
@@ -0,0 +1,342 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use super::{
+    PropertyValue, entry_kind, is_under, parse_properties, relative_path, resolve_in,
+    setting_value, split_frontmatter, walk_files, workdir, write_atomic,
+};
+use crate::MAIN_FOLDER_PREFIX;
+
+// Where recents were kept inside the vault; read until the first write removes it.
+const LEGACY_RECENT_FILE: &str = ".conf/recent.json";
+const RECENT_LIMIT: usize = 200;
+// Bonus for the most recently opened file, fading out towards the end of the list.
+const RECENT_BONUS: i64 = 60;
+// Keeps a heading below its note when both match about equally well.
+const HEADING_PENALTY: i64 = 20;
+
+static QUICK_INDEX: Lazy<RwLock<QuickIndex>> = Lazy::new(|| RwLock::new(QuickIndex::default()));
+
+#[derive(Default)]
+struct QuickIndex {
+    root: Option<PathBuf>,
+    files: HashMap<String, QuickEntry>,
+    recent: Vec<String>,
+}
+
+struct QuickEntry {
+    title: String,
+    kind: &'static str,
+    aliases: Vec<String>,
+    headings: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchField {
+    Title,
+    Path,
+    Alias,
+    Heading,
+}
+
+#[derive(Serialize)]
+pub struct QuickOpenHit {
+    pub path: String,
+    pub title: String,
+    pub kind: String,
+    pub field: MatchField,
+    pub text: String,
+    // Char indices of the matched characters in `text`.
+    pub positions: Vec<usize>,
+    pub score: i64,
+}
+
+struct Candidate<'a> {
+    path: &'a str,
+    entry: &'a QuickEntry,
+    field: MatchField,
+    text: &'a str,
+    score: i64,
+}
+
+fn parse_headings(content: &str) -> Vec<String> {
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+    for line in split_frontmatter(content).1.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let level = trimmed.bytes().take_while(|b| *b == b'#').count();
+        if !(1..=6).contains(&level) {
+            continue;
+        }
+        if let Some(text) = trimmed[level..].strip_prefix([' ', '\t']) {
+            let text = text.trim().trim_end_matches('#').trim_end();
+            if !text.is_empty() {
+                headings.push(text.to_string());
+            }
+        }
+    }
+    headings
+}
+
+fn parse_aliases(content: &str) -> Vec<String> {
+    parse_properties(content)
+        .into_iter()
+        .filter(|p| matches!(p.key.as_str(), "aliases" | "alias"))
+        .flat_map(|p| match p.value {
+            PropertyValue::List(items) => items,
+            PropertyValue::Text(text) => text.split(',').map(String::from).collect(),
+            _ => Vec::new(),
+        })
+        .map(|alias| alias.trim().to_string())
+        .filter(|alias| !alias.is_empty())
+        .collect()
+}
+
+fn read_entry(file: &Path, path: &str) -> QuickEntry {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let kind = entry_kind(name, false);
+    let title = match kind {
+        "note" | "canvas" => name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name),
+        _ => name,
+    };
+    let (aliases, headings) = match name.ends_with(".md") {
+        true => std::fs::read_to_string(file)
+            .map(|content| (parse_aliases(&content), parse_headings(&content)))
+            .unwrap_or_default(),
+        false => Default::default(),
+    };
+    QuickEntry {
+        title: title.to_string(),
+        kind,
+        aliases,
+        headings,
+    }
+}
+
+// Recents are per machine, so they live in the app folder rather than the synced vault.
+fn recent_file(root: &Path) -> PathBuf {
+    MAIN_FOLDER_PREFIX
+        .get()
+        .unwrap()
+        .join("recent")
+        .join(format!(
+            "{}.json",
+            urlencoding::encode(&root.to_string_lossy())
+        ))
+}
+
+fn read_recent(root: &Path) -> Vec<String> {
+    std::fs::read_to_string(recent_file(root))
+        .or_else(|_| std::fs::read_to_string(root.join(LEGACY_RECENT_FILE)))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_recent(root: &Path, recent: &[String]) -> Result<(), String> {
+    let file = recent_file(root);
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string(recent).map_err(|e| e.to_string())?;
+    write_atomic(&file, content.as_bytes()).map_err(|e| e.to_string())?;
+    let _ = std::fs::remove_file(root.join(LEGACY_RECENT_FILE));
+    Ok(())
+}
+
+impl QuickIndex {
+    fn ensure(&mut self, root: &Path) {
+        if self.root.as_deref() == Some(root) {
+            return;
+        }
+        *self = QuickIndex {
+            root: Some(root.to_path_buf()),
+            recent: read_recent(root),
+            ..Default::default()
+        };
+        self.insert_entry(root, "");
+    }
+
+    fn insert_entry(&mut self, root: &Path, path: &str) {
+        for file in walk_files(root, path) {
+            if let Some(relative) = relative_path(root, &file) {
+                let entry = read_entry(&file, &relative);
+                self.files.insert(relative, entry);
+            }
+        }
+    }
+
+    fn remove_entry(&mut self, path: &str) {
+        self.files.retain(|file, _| !is_under(file, path));
+    }
+
+    fn search(&self, query: &str, limit: usize) -> Vec<QuickOpenHit> {
+        let recency = self
+            .recent
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let left = (RECENT_LIMIT - i.min(RECENT_LIMIT)) as i64;
+                (path.as_str(), RECENT_BONUS * left / RECENT_LIMIT as i64)
+            })
+            .collect::<HashMap<_, _>>();
+        let query = query.trim();
+        if query.is_empty() {
+            return self
+                .recent
+                .iter()
+                .filter_map(|path| {
+                    let entry = self.files.get(path)?;
+                    Some(QuickOpenHit {
+                        path: path.clone(),
+                        title: entry.title.clone(),
+                        kind: entry.kind.to_string(),
+                        field: MatchField::Path,
+                        text: path.clone(),
+                        positions: Vec::new(),
+                        score: recency.get(path.as_str()).copied().unwrap_or_default(),
+                    })
+                })
+                .take(limit)
+                .collect();
+        }
+        let matcher = SkimMatcherV2::default();
+        let mut candidates = Vec::new();
+        for (path, entry) in &self.files {
+            let bonus = recency.get(path.as_str()).copied().unwrap_or_default();
+            let best = [(MatchField::Title, &entry.title), (MatchField::Path, path)]
+                .into_iter()
+                .chain(entry.aliases.iter().map(|a| (MatchField::Alias, a)))
+                .filter_map(|(field, text)| Some((matcher.fuzzy_match(text, query)?, field, text)))
+                .max_by_key(|(score, ..)| *score);
+            if let Some((score, field, text)) = best {
+                candidates.push(Candidate {
+                    path,
+                    entry,
+                    field,
+                    text,
+                    score: score + bonus,
+                });
+            }
+            for heading in &entry.headings {
+                if let Some(score) = matcher.fuzzy_match(heading, query) {
+                    candidates.push(Candidate {
+                        path,
+                        entry,
+                        field: MatchField::Heading,
+                        text: heading,
+                        score: score + bonus - HEADING_PENALTY,
+                    });
+                }
+            }
+        }
+        candidates.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.path.len().cmp(&b.path.len()))
+                .then(a.path.cmp(b.path))
+        });
+        // Match positions are only worth computing for what gets returned.
+        candidates
+            .into_iter()
+            .take(limit)
+            .map(|c| QuickOpenHit {
+                path: c.path.to_string(),
+                title: c.entry.title.clone(),
+                kind: c.entry.kind.to_string(),
+                field: c.field,
+                text: c.text.to_string(),
+                positions: matcher
+                    .fuzzy_indices(c.text, query)
+                    .map(|(_, positions)| positions)
+                    .unwrap_or_default(),
+                score: c.score,
+            })
+            .collect()
+    }
+}
+
+pub fn index_entry_quick_open(root: &Path, path: &str) {
+    let mut index = QUICK_INDEX.write().unwrap();
+    if index.root.as_deref() != Some(root) {
+        return;
+    }
+    index.remove_entry(path);
+    index.insert_entry(root, path);
+}
+
+pub fn forget_entry_quick_open(root: &Path, path: &str) {
+    let mut index = QUICK_INDEX.write().unwrap();
+    if index.root.as_deref() == Some(root) {
+        index.remove_entry(path);
+    }
+}
+
+pub fn reset_quick_open() {
+    QUICK_INDEX.write().unwrap().root = None;
+}
+
+#[tauri::command]
+pub async fn quick_open(query: String, limit: Option<usize>) -> Result<Vec<QuickOpenHit>, String> {
+    let workdir = workdir().await?;
+    let limit = match limit {
+        Some(limit) => limit,
+        None => setting_value("quick_open.limit")
+            .await
+            .as_u64()
+            .unwrap_or(50) as usize,
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut index = QUICK_INDEX.write().unwrap();
+        index.ensure(&workdir);
+        index.search(&query, limit)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn record_quick_open(path: String) -> Result<(), String> {
+    let workdir = workdir().await?;
+    let relative = resolve_in(&workdir, &path)?.relative;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut index = QUICK_INDEX.write().unwrap();
+        index.ensure(&workdir);
+        index.recent.retain(|p| p != &relative);
+        index.recent.insert(0, relative);
+        index.recent.truncate(RECENT_LIMIT);
+        write_recent(&workdir, &index.recent)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,444 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::{
+    DB, PropertyValue, is_under, normalize_path, parse_properties, relative_path, setting_value,
+    walk_files, workdir,
+};
+
+const SEARCH_VERSION: u32 = 1;
+
+static SYNCED_ROOT: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+const SCHEMA: &str = "
+    DEFINE ANALYZER IF NOT EXISTS vault_analyzer TOKENIZERS blank, class, punct FILTERS lowercase, snowball(english);
+    DEFINE TABLE IF NOT EXISTS search_doc SCHEMALESS;
+    DEFINE INDEX IF NOT EXISTS search_doc_content ON search_doc FIELDS content SEARCH ANALYZER vault_analyzer BM25 HIGHLIGHTS;
+";
+
+// Match delimiters for `search::highlight`, see `snippet`.
+const MARK_OPEN: char = '\u{E000}';
+const MARK_CLOSE: char = '\u{E001}';
+
+const SEARCH_QUERY: &str = "
+    SELECT path, title, kind, search::score(1) AS score, search::highlight($open, $close, 1) AS highlighted
+    FROM search_doc
+    WHERE content @1@ $query
+        AND ($folder = NONE OR string::starts_with(path, $folder))
+        AND ($kind = NONE OR kind = $kind)
+        AND ($property = NONE OR array::len(properties[WHERE key = $property AND ($value = NONE OR value = $value)]) > 0)
+    ORDER BY score DESC
+    LIMIT $limit
+";
+
+#[derive(Serialize, Deserialize)]
+struct SearchDoc {
+    path: String,
+    title: String,
+    kind: String,
+    content: String,
+    properties: Vec<SearchProperty>,
+    modified: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SearchProperty {
+    key: String,
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct DocStamp {
+    path: String,
+    modified: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SearchMeta {
+    root: String,
+    #[serde(default)]
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct SearchRow {
+    path: String,
+    title: String,
+    kind: String,
+    score: Option<f64>,
+    highlighted: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct SearchFilters {
+    pub folder: Option<String>,
+    pub kind: Option<String>,
+    pub property: Option<String>,
+    pub value: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct SearchHit {
+    pub path: String,
+    pub title: String,
+    pub kind: String,
+    pub score: f64,
+    pub snippet: String,
+}
+
+fn document_kind(path: &str) -> Option<&'static str> {
+    if path.ends_with(".md") {
+        Some("note")
+    } else if path.ends_with(".canvas") {
+        Some("canvas")
+    } else {
+        None
+    }
+}
+
+fn modified_time(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn collect_canvas_text(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match (key.as_str(), value) {
+                    ("text", serde_json::Value::String(text)) => out.push(text.clone()),
+                    _ => collect_canvas_text(value, out),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_canvas_text(item, out);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn search_properties(content: &str) -> Vec<SearchProperty> {
+    let mut rows = Vec::new();
+    for property in parse_properties(content) {
+        let values = match property.value {
+            PropertyValue::Text(v) | PropertyValue::Date(v) | PropertyValue::Link(v) => vec![v],
+            PropertyValue::Number(n) => vec![n.to_string()],
+            PropertyValue::Checkbox(b) => vec![b.to_string()],
+            PropertyValue::List(items) => items,
+        };
+        rows.extend(values.into_iter().map(|value| SearchProperty {
+            key: property.key.clone(),
+            value,
+        }));
+    }
+    rows
+}
+
+fn read_document(root: &Path, path: &str) -> Option<SearchDoc> {
+    let kind = document_kind(path)?;
+    let file = root.join(path);
+    let raw = std::fs::read_to_string(&file).ok()?;
+    let properties = match kind {
+        "note" => search_properties(&raw),
+        _ => Vec::new(),
+    };
+    let body = match kind {
+        "canvas" => match serde_json::from_str::<serde_json::Value>(&raw) {
+            Ok(value) => {
+                let mut text = Vec::new();
+                collect_canvas_text(&value, &mut text);
+                text.join("\n")
+            }
+            Err(_) => raw,
+        },
+        _ => raw,
+    };
+    let title = path
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+        .trim_end_matches(".md")
+        .trim_end_matches(".canvas")
+        .to_string();
+    Some(SearchDoc {
+        path: path.to_string(),
+        content: format!("{}\n{}", title, body),
+        title,
+        kind: kind.to_string(),
+        properties,
+        modified: modified_time(&file),
+    })
+}
+
+// Note text is untrusted (it may arrive through sync) and the snippet is rendered as HTML,
+// so matches are delimited with private-use characters and only turned into `<mark>` after
+// the text around them has been escaped.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn snippet(highlighted: &str) -> String {
+    let center = highlighted.find(MARK_OPEN).unwrap_or(0);
+    let mut start = center.saturating_sub(80);
+    while !highlighted.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (center + 160).min(highlighted.len());
+    while !highlighted.is_char_boundary(end) {
+        end += 1;
+    }
+    let window = highlighted[start..end].replace(['\n', '\r'], " ");
+    let mut snippet = String::new();
+    let mut open = false;
+    for c in escape_html(window.trim()).chars() {
+        match c {
+            MARK_OPEN if !open => snippet.push_str("<mark>"),
+            MARK_CLOSE if open => snippet.push_str("</mark>"),
+            MARK_OPEN | MARK_CLOSE => continue,
+            c => {
+                snippet.push(c);
+                continue;
+            }
+        }
+        open = !open;
+    }
+    if open {
+        snippet.push_str("</mark>");
+    }
+    snippet
+}
+
+async fn write_document(root: &Path, path: &str) -> Result<(), String> {
+    let db = &DB.get().unwrap().database;
+    match read_document(root, path) {
+        Some(doc) => {
+            db.upsert::<Option<SearchDoc>>(("search_doc", path))
+                .content(doc)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        None => {
+            db.delete::<Option<SearchDoc>>(("search_doc", path))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+async fn ensure_synced(root: &Path) -> Result<(), String> {
+    let mut synced = SYNCED_ROOT.lock().await;
+    if synced.as_deref() == Some(root) {
+        return Ok(());
+    }
+    let db = &DB.get().unwrap().database;
+    db.query(SCHEMA)
+        .await
+        .map_err(|e| e.to_string())?
+        .check()
+        .map_err(|e| e.to_string())?;
+    let root_name = root.to_string_lossy().to_string();
+    let meta: Option<SearchMeta> = db
+        .select(("search_meta", "root"))
+        .await
+        .map_err(|e| e.to_string())?;
+    if meta.map(|m| (m.root, m.version)) != Some((root_name.clone(), SEARCH_VERSION)) {
+        db.query("DELETE search_doc")
+            .await
+            .map_err(|e| e.to_string())?;
+        db.upsert::<Option<SearchMeta>>(("search_meta", "root"))
+            .content(SearchMeta {
+                root: root_name,
+                version: SEARCH_VERSION,
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    let mut known = db
+        .query("SELECT path, modified FROM search_doc")
+        .await
+        .map_err(|e| e.to_string())?
+        .take::<Vec<DocStamp>>(0)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|d| (d.path, d.modified))
+        .collect::<HashMap<_, _>>();
+    for file in walk_files(root, "") {
+        let Some(path) = relative_path(root, &file) else {
+            continue;
+        };
+        if document_kind(&path).is_none() {
+            continue;
+        }
+        if known.remove(&path) != Some(modified_time(&file)) {
+            write_document(root, &path).await?;
+        }
+    }
+    for path in known.keys() {
+        db.delete::<Option<SearchDoc>>(("search_doc", path.as_str()))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    *synced = Some(root.to_path_buf());
+    Ok(())
+}
+
+pub async fn forget_entry_search(root: &Path, path: &str) {
+    let synced = SYNCED_ROOT.lock().await;
+    if synced.as_deref() != Some(root) {
+        return;
+    }
+    let db = &DB.get().unwrap().database;
+    let _ = db
+        .query("DELETE search_doc WHERE path = $path OR string::starts_with(path, $prefix)")
+        .bind(("path", path.to_string()))
+        .bind(("prefix", format!("{}/", path)))
+        .await;
+}
+
+pub async fn index_entry_search(root: &Path, path: &str) {
+    forget_entry_search(root, path).await;
+    let synced = SYNCED_ROOT.lock().await;
+    if synced.as_deref() != Some(root) {
+        return;
+    }
+    for file in walk_files(root, path) {
+        if let Some(relative) = relative_path(root, &file) {
+            if is_under(&relative, path) {
+                let _ = write_document(root, &relative).await;
+            }
+        }
+    }
+}
+
+pub async fn reset_search_index() {
+    *SYNCED_ROOT.lock().await = None;
+}
+
+pub async fn sync_search_index() -> Result<(), String> {
+    ensure_synced(&workdir().await?).await
+}
+
+#[tauri::command]
+pub async fn search_notes(
+    query: String,
+    filters: Option<SearchFilters>,
+) -> Result<Vec<SearchHit>, String> {
+    let workdir = workdir().await?;
+    ensure_synced(&workdir).await?;
+    let filters = filters.unwrap_or_default();
+    let limit = match filters.limit {
+        Some(limit) => limit,
+        None => setting_value("search.limit").await.as_u64().unwrap_or(50) as usize,
+    };
+    let folder = filters
+        .folder
+        .map(|f| normalize_path(&f))
+        .filter(|f| !f.is_empty())
+        .map(|f| format!("{}/", f));
+    let rows = DB
+        .get()
+        .unwrap()
+        .database
+        .query(SEARCH_QUERY)
+        .bind(("query", query))
+        .bind(("folder", folder))
+        .bind(("kind", filters.kind))
+        .bind(("property", filters.property))
+        .bind(("value", filters.value))
+        .bind(("limit", limit))
+        .bind(("open", MARK_OPEN.to_string()))
+        .bind(("close", MARK_CLOSE.to_string()))
+        .await
+        .map_err(|e| e.to_string())?
+        .take::<Vec<SearchRow>>(0)
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .map(|row| SearchHit {
+            snippet: row.highlighted.as_deref().map(snippet).unwrap_or_default(),
+            path: row.path,
+            title: row.title,
+            kind: row.kind,
+            score: row.score.unwrap_or_default(),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn rebuild_search_index() -> Result<(), String> {
+    *SYNCED_ROOT.lock().await = None;
+    DB.get()
+        .unwrap()
+        .database
+        .query("DELETE search_doc")
+        .await
+        .map_err(|e| e.to_string())?;
+    sync_search_index().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippet_escapes_note_markup() {
+        let highlighted = format!(
+            "<img src=x onerror=\"alert(1)\"> a {}match{} & more",
+            MARK_OPEN, MARK_CLOSE
+        );
+        assert_eq!(
+            snippet(&highlighted),
+            "&lt;img src=x onerror=&quot;alert(1)&quot;&gt; a <mark>match</mark> &amp; more"
+        );
+    }
+
+    #[test]
+    fn snippet_closes_a_cut_match() {
+        let highlighted = format!("{}{}{}", MARK_OPEN, "x".repeat(200), MARK_CLOSE);
+        let snippet = snippet(&highlighted);
+        assert!(snippet.starts_with("<mark>") && snippet.ends_with("</mark>"));
+        assert_eq!(snippet.matches("<mark>").count(), 1);
+    }
+}
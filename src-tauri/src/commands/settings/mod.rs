@@ -13,12 +13,362 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::fs;
-use std::path::Path;
+use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::Emitter;
+use thiserror::Error;
+
+use super::{DB, Entity, IGNORE_SETTING, refresh_global_ignore, update_vault_config, vault_config};
+
+#[derive(Error, Debug)]
+pub enum SettingsError {
+    #[error("Неизвестная настройка `{0}`")]
+    UnknownKey(String),
+
+    #[error("Недопустимое значение настройки `{0}`")]
+    InvalidValue(String),
+
+    #[error("Некорректный файл настроек: {0}")]
+    InvalidImport(String),
+
+    #[error("Ошибка базы данных: {0}")]
+    Db(String),
+}
+
+impl From<surrealdb::Error> for SettingsError {
+    fn from(e: surrealdb::Error) -> Self {
+        SettingsError::Db(e.to_string())
+    }
+}
+
+impl From<SettingsError> for String {
+    fn from(e: SettingsError) -> Self {
+        e.to_string()
+    }
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SettingKind {
+    Bool {
+        default: bool,
+    },
+    Integer {
+        default: i64,
+        min: i64,
+        max: i64,
+    },
+    Text {
+        default: &'static str,
+    },
+    Choice {
+        default: &'static str,
+        options: &'static [&'static str],
+    },
+    List {
+        default: &'static [&'static str],
+    },
+}
+
+struct SettingSpec {
+    key: &'static str,
+    kind: SettingKind,
+}
+
+const SCHEMA: &[SettingSpec] = &[
+    SettingSpec {
+        key: "appearance.theme",
+        kind: SettingKind::Text { default: "lotm" },
+    },
+    SettingSpec {
+        key: "explorer.sort",
+        kind: SettingKind::Choice {
+            default: "name",
+            options: &["name", "modified", "created", "size", "kind"],
+        },
+    },
+    SettingSpec {
+        key: "explorer.folders_first",
+        kind: SettingKind::Bool { default: true },
+    },
+    SettingSpec {
+        key: "explorer.show_hidden",
+        kind: SettingKind::Bool { default: false },
+    },
+    SettingSpec {
+        key: IGNORE_SETTING,
+        kind: SettingKind::List { default: &[] },
+    },
+    SettingSpec {
+        key: "search.limit",
+        kind: SettingKind::Integer {
+            default: 50,
+            min: 1,
+            max: 500,
+        },
+    },
+    SettingSpec {
+        key: "quick_open.limit",
+        kind: SettingKind::Integer {
+            default: 50,
+            min: 1,
+            max: 500,
+        },
+    },
+];
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingSource {
+    Default,
+    User,
+    Vault,
+}
+
+#[derive(Serialize)]
+pub struct Setting {
+    pub key: String,
+    #[serde(flatten)]
+    pub kind: SettingKind,
+    pub value: Value,
+    pub source: SettingSource,
+}
+
+// Values are kept as JSON text, like the other entities keep plain strings.
+#[derive(Serialize, Deserialize, Clone, Entity)]
+#[entity(table = "setting")]
+pub struct StoredSetting {
+    pub key: String,
+    pub value: String,
+}
+
+impl SettingKind {
+    fn default_value(&self) -> Value {
+        match self {
+            SettingKind::Bool { default } => Value::from(*default),
+            SettingKind::Integer { default, .. } => Value::from(*default),
+            SettingKind::Text { default } | SettingKind::Choice { default, .. } => {
+                Value::from(*default)
+            }
+            SettingKind::List { default } => Value::from(default.to_vec()),
+        }
+    }
+
+    fn accepts(&self, value: &Value) -> bool {
+        match (self, value) {
+            (SettingKind::Bool { .. }, Value::Bool(_)) => true,
+            (SettingKind::Integer { min, max, .. }, Value::Number(n)) => {
+                n.as_i64().is_some_and(|n| (*min..=*max).contains(&n))
+            }
+            (SettingKind::Text { .. }, Value::String(_)) => true,
+            (SettingKind::Choice { options, .. }, Value::String(s)) => {
+                options.contains(&s.as_str())
+            }
+            (SettingKind::List { .. }, Value::Array(items)) => items.iter().all(Value::is_string),
+            _ => false,
+        }
+    }
+}
+
+fn spec(key: &str) -> Result<&'static SettingSpec, SettingsError> {
+    SCHEMA
+        .iter()
+        .find(|s| s.key == key)
+        .ok_or_else(|| SettingsError::UnknownKey(key.to_string()))
+}
+
+fn validate(key: &str, value: &Value) -> Result<&'static SettingSpec, SettingsError> {
+    let spec = spec(key)?;
+    match spec.kind.accepts(value) {
+        true => Ok(spec),
+        false => Err(SettingsError::InvalidValue(key.to_string())),
+    }
+}
+
+// Stored values that no longer fit the schema are skipped and fall back to defaults.
+async fn user_values() -> Result<HashMap<String, Value>, SettingsError> {
+    let stored = DB
+        .get()
+        .unwrap()
+        .database
+        .select::<Vec<StoredSetting>>(StoredSetting::TABLE)
+        .await?;
+    Ok(stored
+        .into_iter()
+        .filter_map(|s| Some((s.key, serde_json::from_str(&s.value).ok()?)))
+        .collect())
+}
+
+// The open vault's `.conf/vault.json` may pin settings for everyone using the vault.
+async fn vault_values() -> Map<String, Value> {
+    vault_config()
+        .await
+        .map(|config| config.settings)
+        .unwrap_or_default()
+}
+
+fn resolve(
+    spec: &SettingSpec,
+    user: &HashMap<String, Value>,
+    vault: &Map<String, Value>,
+) -> Setting {
+    let (value, source) = [
+        (vault.get(spec.key), SettingSource::Vault),
+        (user.get(spec.key), SettingSource::User),
+    ]
+    .into_iter()
+    .find_map(|(value, source)| {
+        value
+            .filter(|v| spec.kind.accepts(v))
+            .map(|v| (v.clone(), source))
+    })
+    .unwrap_or_else(|| (spec.kind.default_value(), SettingSource::Default));
+    Setting {
+        key: spec.key.to_string(),
+        kind: spec.kind,
+        value,
+        source,
+    }
+}
+
+// The value in effect for `key`, for backend code that follows a setting.
+pub async fn setting_value(key: &str) -> Value {
+    let Ok(spec) = spec(key) else {
+        return Value::Null;
+    };
+    let user = user_values().await.unwrap_or_default();
+    resolve(spec, &user, &vault_values().await).value
+}
+
+async fn current(spec: &SettingSpec) -> Result<Setting, SettingsError> {
+    Ok(resolve(spec, &user_values().await?, &vault_values().await))
+}
+
+async fn store(key: &str, value: &Value) -> Result<(), SettingsError> {
+    DB.get()
+        .unwrap()
+        .database
+        .upsert::<Option<StoredSetting>>((StoredSetting::TABLE, key))
+        .content(StoredSetting {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+        .await?;
+    Ok(())
+}
+
+async fn announce(app: &tauri::AppHandle, setting: &Setting) {
+    if setting.key == IGNORE_SETTING {
+        refresh_global_ignore().await;
+    }
+    let _ = app.emit("settings-changed", setting);
+}
+
+#[tauri::command]
+pub async fn get_settings_list() -> Result<Vec<Setting>, String> {
+    let user = user_values().await?;
+    let vault = vault_values().await;
+    Ok(SCHEMA
+        .iter()
+        .map(|spec| resolve(spec, &user, &vault))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_setting(key: String) -> Result<Setting, String> {
+    Ok(current(spec(&key)?).await?)
+}
+
+#[tauri::command]
+pub async fn set_setting(
+    key: String,
+    value: Value,
+    app: tauri::AppHandle,
+) -> Result<Setting, String> {
+    let spec = validate(&key, &value)?;
+    store(&key, &value).await?;
+    let setting = current(spec).await?;
+    announce(&app, &setting).await;
+    Ok(setting)
+}
+
+// Pins `key` for the open vault, a missing `value` removes the pin.
+#[tauri::command]
+pub async fn set_vault_setting(
+    key: String,
+    value: Option<Value>,
+    app: tauri::AppHandle,
+) -> Result<Setting, String> {
+    let spec = match &value {
+        Some(value) => validate(&key, value)?,
+        None => spec(&key)?,
+    };
+    update_vault_config(
+        |config| {
+            match value {
+                Some(value) => config.settings.insert(key, value),
+                None => config.settings.remove(&key),
+            };
+        },
+        &app,
+    )
+    .await?;
+    let setting = current(spec).await?;
+    announce(&app, &setting).await;
+    Ok(setting)
+}
+
+#[tauri::command]
+pub async fn reset_setting(key: String, app: tauri::AppHandle) -> Result<Setting, String> {
+    let spec = spec(&key)?;
+    DB.get()
+        .unwrap()
+        .database
+        .delete::<Option<StoredSetting>>((StoredSetting::TABLE, key.as_str()))
+        .await
+        .map_err(SettingsError::from)?;
+    let setting = current(spec).await?;
+    announce(&app, &setting).await;
+    Ok(setting)
+}
+
+// Only values the user changed are exported, so defaults keep following the app.
+#[tauri::command]
+pub async fn export_settings() -> Result<String, String> {
+    let user = user_values().await?;
+    let values = SCHEMA
+        .iter()
+        .filter_map(|spec| {
+            let value = user.get(spec.key).filter(|v| spec.kind.accepts(v))?;
+            Some((spec.key.to_string(), value.clone()))
+        })
+        .collect::<Map<_, _>>();
+    serde_json::to_string_pretty(&values).map_err(|e| e.to_string())
+}
+
+// Checks the whole file before writing anything, so a bad import changes nothing.
 #[tauri::command]
-pub fn get_settings_list() -> String {
-    let path = Path::new("./settings/settings.json");
-    let file = fs::read(path).unwrap();
-    String::from_utf8(file).unwrap()
+pub async fn import_settings(
+    content: String,
+    app: tauri::AppHandle,
+) -> Result<Vec<Setting>, String> {
+    let values = serde_json::from_str::<Map<String, Value>>(&content)
+        .map_err(|e| SettingsError::InvalidImport(e.to_string()))?;
+    for (key, value) in &values {
+        validate(key, value)?;
+    }
+    for (key, value) in &values {
+        store(key, value).await?;
+    }
+    let user = user_values().await?;
+    let vault = vault_values().await;
+    let mut changed = Vec::new();
+    for key in values.keys() {
+        let setting = resolve(spec(key)?, &user, &vault);
+        announce(&app, &setting).await;
+        changed.push(setting);
+    }
+    Ok(changed)
 }
@@ -13,20 +13,20 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use super::{DB, SideRepo, SideRepoField};
+use super::{DB, Entity, SideRepo, SideRepoFields};
 
 #[tauri::command]
 pub async fn add_repo(rtype: String, link: String, app: tauri::AppHandle) {
     let db = DB.get().unwrap();
-    let side_repo: Vec<SideRepoField> = vec![
-        SideRepoField::Type(rtype),
-        SideRepoField::Link(link.clone()),
+    let side_repo: Vec<SideRepoFields> = vec![
+        SideRepoFields::RepoType(rtype),
+        SideRepoFields::Link(link.clone()),
     ];
     let linkparts = link.split('/').collect::<Vec<&str>>();
-    db.create::<SideRepoField, SideRepo>(
+    db.create::<SideRepoFields, SideRepo>(
         side_repo,
         app.clone(),
-        "side_repo",
+        SideRepo::TABLE,
         linkparts.get(2).unwrap(),
     )
     .await
@@ -36,7 +36,7 @@ pub async fn add_repo(rtype: String, link: String, app: tauri::AppHandle) {
 #[tauri::command]
 pub async fn get_repos_list() -> Result<Vec<SideRepo>, String> {
     let db = DB.get().unwrap();
-    db.get_all_members::<SideRepo>("side_repo")
+    db.get_all_members::<SideRepo>(SideRepo::TABLE)
         .await
         .map_err(|e| e.to_string())
 }
@@ -45,7 +45,9 @@ pub async fn get_repos_list() -> Result<Vec<SideRepo>, String> {
 pub async fn delete_repo(link: String) -> Result<(), String> {
     let db = DB.get().unwrap();
     let result = db
-        .delete("side_repo", "link", link)
+        .query::<SideRepo>()
+        .filter(SideRepoFields::Link(link))
+        .delete()
         .await
         .map_err(|e| e.to_string());
     println!("{:#?}", result);
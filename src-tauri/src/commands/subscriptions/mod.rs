@@ -0,0 +1,232 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    collections::HashMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use surrealdb::Action;
+use tauri::{AppHandle, Emitter, Manager, async_runtime::JoinHandle};
+
+use super::{
+    DB, Entity, ListQuery, PluginList, SideRepo, StoredSetting, ThemeRepo, Vault,
+    apply_vault_plugins,
+};
+
+static NEXT_SUBSCRIPTION: AtomicU64 = AtomicU64::new(1);
+
+static SUBSCRIPTIONS: Lazy<Mutex<HashMap<u64, Subscription>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct Subscription {
+    window: String,
+    // Aborting the task drops the live stream, which kills the query in SurrealDB.
+    task: JoinHandle<()>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Topic {
+    Plugins,
+    Themes,
+    Repos,
+    Vaults,
+    Settings,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Serialize, Clone)]
+pub struct EntityChange<T> {
+    pub subscription: u64,
+    pub table: &'static str,
+    pub action: ChangeAction,
+    pub record: T,
+}
+
+// Lets a topic adjust records the way its list command does before they are sent.
+pub trait Published: Sized {
+    fn publish(records: &mut [Self]) -> impl Future<Output = ()> + Send {
+        let _ = records;
+        async {}
+    }
+}
+
+impl Published for PluginList {
+    fn publish(records: &mut [Self]) -> impl Future<Output = ()> + Send {
+        apply_vault_plugins(records)
+    }
+}
+
+impl Published for ThemeRepo {}
+impl Published for SideRepo {}
+impl Published for Vault {}
+impl Published for StoredSetting {}
+
+// Sends the records currently matching `query` as creations, then forwards every
+// later change to the window that asked for it. The live query is started first
+// so nothing written in between is lost; a record created in between shows up in
+// both and its live creation is dropped.
+async fn forward<T>(
+    app: AppHandle,
+    window: String,
+    id: u64,
+    query: ListQuery,
+) -> Result<JoinHandle<()>, String>
+where
+    T: Entity + Published + DeserializeOwned + Serialize + Clone + Send + Unpin + 'static,
+{
+    let db = DB.get().unwrap();
+    let mut stream = db
+        .query::<T>()
+        .apply(query.clone())
+        .live()
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut snapshot = db
+        .query::<T>()
+        .apply(query)
+        .fetch()
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut seen = snapshot
+        .iter()
+        .filter_map(|record| serde_json::to_value(record).ok())
+        .collect::<Vec<_>>();
+    Ok(tauri::async_runtime::spawn(async move {
+        T::publish(&mut snapshot).await;
+        for record in snapshot {
+            let change = EntityChange {
+                subscription: id,
+                table: T::TABLE,
+                action: ChangeAction::Create,
+                record,
+            };
+            if app
+                .emit_to(window.as_str(), "entity-changed", change)
+                .is_err()
+            {
+                return;
+            }
+        }
+        while let Some(notification) = stream.next().await {
+            let notification = match notification {
+                Ok(notification) => notification,
+                Err(e) => {
+                    eprintln!("Ошибка подписки {}: {}", T::TABLE, e);
+                    continue;
+                }
+            };
+            let action = match notification.action {
+                Action::Create => ChangeAction::Create,
+                Action::Update => ChangeAction::Update,
+                Action::Delete => ChangeAction::Delete,
+                _ => continue,
+            };
+            if let ChangeAction::Create = action {
+                let value = serde_json::to_value(&notification.data).ok();
+                if let Some(i) = seen.iter().position(|v| Some(v) == value.as_ref()) {
+                    seen.swap_remove(i);
+                    continue;
+                }
+            }
+            let mut records = [notification.data];
+            T::publish(&mut records).await;
+            let [record] = records;
+            let change = EntityChange {
+                subscription: id,
+                table: T::TABLE,
+                action,
+                record,
+            };
+            if app
+                .emit_to(window.as_str(), "entity-changed", change)
+                .is_err()
+            {
+                break;
+            }
+        }
+    }))
+}
+
+fn cancel(subscriptions: impl IntoIterator<Item = Subscription>) {
+    for subscription in subscriptions {
+        subscription.task.abort();
+    }
+}
+
+// Called when a window is destroyed, since its listeners are gone with it.
+pub fn forget_window_subscriptions(window: &str) {
+    let mut subscriptions = SUBSCRIPTIONS.lock().unwrap();
+    let ids = subscriptions
+        .iter()
+        .filter(|(_, s)| s.window == window)
+        .map(|(id, _)| *id)
+        .collect::<Vec<_>>();
+    cancel(ids.iter().filter_map(|id| subscriptions.remove(id)));
+}
+
+#[tauri::command]
+pub async fn subscribe(
+    topic: Topic,
+    query: Option<ListQuery>,
+    window: tauri::Window,
+    app: AppHandle,
+) -> Result<u64, String> {
+    let id = NEXT_SUBSCRIPTION.fetch_add(1, Ordering::Relaxed);
+    let label = window.label().to_string();
+    let query = query.unwrap_or_default();
+    let sender = app.clone();
+    let task = match topic {
+        Topic::Plugins => forward::<PluginList>(sender, label.clone(), id, query).await?,
+        Topic::Themes => forward::<ThemeRepo>(sender, label.clone(), id, query).await?,
+        Topic::Repos => forward::<SideRepo>(sender, label.clone(), id, query).await?,
+        Topic::Vaults => forward::<Vault>(sender, label.clone(), id, query).await?,
+        Topic::Settings => forward::<StoredSetting>(sender, label.clone(), id, query).await?,
+    };
+    SUBSCRIPTIONS.lock().unwrap().insert(
+        id,
+        Subscription {
+            window: label.clone(),
+            task,
+        },
+    );
+    // The window may have been destroyed while the queries were starting, after
+    // `forget_window_subscriptions` already ran for it.
+    if app.get_webview_window(&label).is_none() {
+        forget_window_subscriptions(&label);
+        return Err("окно уже закрыто".to_string());
+    }
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn unsubscribe(id: u64) {
+    let removed = SUBSCRIPTIONS.lock().unwrap().remove(&id);
+    cancel(removed);
+}
@@ -0,0 +1,639 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use git2::{
+    AnnotatedCommit, Cred, CredentialType, ErrorCode, FetchOptions, IndexAddOption, IndexConflict,
+    Oid, PushOptions, RemoteCallbacks, Repository, RepositoryInitOptions, Signature, StatusOptions,
+    build::CheckoutBuilder,
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::Emitter;
+
+use super::{entry_updated, sync_ignore_rules, workdir};
+
+// Conflicts of each vault that are still waiting for the user, one per path.
+static LAST_CONFLICTS: Lazy<Mutex<HashMap<PathBuf, Vec<SyncConflict>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Clone)]
+pub struct SyncConflict {
+    pub path: String,
+    pub resolution: String,
+    pub copy: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SyncStatus {
+    pub configured: bool,
+    pub remote: Option<String>,
+    pub branch: String,
+    pub ahead: usize,
+    pub behind: usize,
+    pub changed: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SyncReport {
+    pub committed: bool,
+    pub integration: String,
+    pub pushed: bool,
+    pub updated: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+    pub status: SyncStatus,
+}
+
+struct SyncSettings {
+    branch: String,
+    strategy: String,
+    conflicts: String,
+}
+
+fn sync_settings(repo: &Repository) -> SyncSettings {
+    let config = repo.config().ok();
+    let get = |key: &str| config.as_ref().and_then(|c| c.get_string(key).ok());
+    let head = repo
+        .head()
+        .ok()
+        .and_then(|h| h.shorthand().map(String::from));
+    SyncSettings {
+        branch: get("void.sync.branch")
+            .or(head)
+            .unwrap_or_else(|| "main".to_string()),
+        strategy: get("void.sync.strategy").unwrap_or_else(|| "merge".to_string()),
+        conflicts: get("void.sync.conflicts").unwrap_or_else(|| "markers".to_string()),
+    }
+}
+
+// The ignore rules only live on this handle, so `add_all` and status skip ignored
+// paths without touching `.gitignore` or `.git/info/exclude`.
+fn open_repo(workdir: &Path) -> Result<Repository, git2::Error> {
+    let repo = Repository::open(workdir)?;
+    repo.add_ignore_rule(&sync_ignore_rules(workdir))?;
+    Ok(repo)
+}
+
+fn signature(repo: &Repository) -> Result<Signature<'static>, git2::Error> {
+    repo.signature()
+        .or_else(|_| Signature::now("VOID", "void@localhost"))
+}
+
+fn callbacks<'a>() -> RemoteCallbacks<'a> {
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(|url, username, allowed| {
+        if allowed.contains(CredentialType::SSH_KEY) {
+            return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+        }
+        let config = git2::Config::open_default()?;
+        Cred::credential_helper(&config, url, username)
+    });
+    callbacks
+}
+
+fn commit_all(repo: &Repository, message: &str) -> Result<bool, git2::Error> {
+    let mut index = repo.index()?;
+    index.add_all(["*"].iter(), IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"].iter(), None)?;
+    index.write()?;
+    let tree_id = index.write_tree()?;
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let unchanged = match &parent {
+        Some(parent) => parent.tree_id() == tree_id,
+        None => index.is_empty(),
+    };
+    if unchanged {
+        return Ok(false);
+    }
+    let tree = repo.find_tree(tree_id)?;
+    let signature = signature(repo)?;
+    let parents = parent.iter().collect::<Vec<_>>();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?;
+    Ok(true)
+}
+
+fn upstream_ref(branch: &str) -> String {
+    format!("refs/remotes/origin/{}", branch)
+}
+
+fn changed_files(
+    repo: &Repository,
+    from: Option<Oid>,
+    to: Oid,
+) -> Result<Vec<String>, git2::Error> {
+    let old = match from {
+        Some(id) => Some(repo.find_commit(id)?.tree()?),
+        None => None,
+    };
+    let new = repo.find_commit(to)?.tree()?;
+    let diff = repo.diff_tree_to_tree(old.as_ref(), Some(&new), None)?;
+    Ok(diff
+        .deltas()
+        .filter_map(|d| d.new_file().path().or(d.old_file().path()))
+        .filter_map(|p| p.to_str().map(String::from))
+        .collect())
+}
+
+fn conflict_copy_path(path: &str, tag: &str) -> String {
+    match path.rsplit_once('.') {
+        Some((stem, extension)) if !extension.contains('/') => {
+            format!("{} (conflict copy {}).{}", stem, tag, extension)
+        }
+        _ => format!("{} (conflict copy {})", path, tag),
+    }
+}
+
+fn resolve_conflict(
+    repo: &Repository,
+    workdir: &Path,
+    conflict: &IndexConflict,
+    mode: &str,
+    tag: &str,
+) -> Result<Option<SyncConflict>, git2::Error> {
+    let entry = conflict
+        .our
+        .as_ref()
+        .or(conflict.their.as_ref())
+        .or(conflict.ancestor.as_ref());
+    let Some(path) = entry.and_then(|e| String::from_utf8(e.path.clone()).ok()) else {
+        return Ok(None);
+    };
+    let (Some(our), Some(their)) = (&conflict.our, &conflict.their) else {
+        let kept = conflict.our.as_ref().or(conflict.their.as_ref());
+        if let Some(kept) = kept {
+            std::fs::write(workdir.join(&path), repo.find_blob(kept.id)?.content())
+                .map_err(|e| git2::Error::from_str(&e.to_string()))?;
+        }
+        return Ok(None);
+    };
+    if mode == "markers" && path.ends_with(".md") {
+        return Ok(Some(SyncConflict {
+            path,
+            resolution: "markers".to_string(),
+            copy: None,
+        }));
+    }
+    let copy = conflict_copy_path(&path, tag);
+    let write = |target: &str, id: Oid| -> Result<(), git2::Error> {
+        std::fs::write(workdir.join(target), repo.find_blob(id)?.content())
+            .map_err(|e| git2::Error::from_str(&e.to_string()))
+    };
+    write(&path, our.id)?;
+    write(&copy, their.id)?;
+    Ok(Some(SyncConflict {
+        path,
+        resolution: "copy".to_string(),
+        copy: Some(copy),
+    }))
+}
+
+fn merge_upstream(
+    repo: &Repository,
+    workdir: &Path,
+    upstream: &AnnotatedCommit,
+    settings: &SyncSettings,
+) -> Result<Vec<SyncConflict>, git2::Error> {
+    let mut checkout = CheckoutBuilder::new();
+    checkout.allow_conflicts(true).conflict_style_merge(true);
+    repo.merge(&[upstream], None, Some(&mut checkout))?;
+    let mut index = repo.index()?;
+    let mut conflicts = Vec::new();
+    if index.has_conflicts() {
+        let tag = upstream.id().to_string()[..7].to_string();
+        let entries = index.conflicts()?.collect::<Result<Vec<_>, _>>()?;
+        for conflict in &entries {
+            if let Some(resolved) =
+                resolve_conflict(repo, workdir, conflict, &settings.conflicts, &tag)?
+            {
+                conflicts.push(resolved);
+            }
+        }
+        index.add_all(["*"].iter(), IndexAddOption::DEFAULT, None)?;
+        index.update_all(["*"].iter(), None)?;
+        for conflict in &entries {
+            if let Some(entry) = conflict.our.as_ref().or(conflict.their.as_ref()) {
+                let path = PathBuf::from(String::from_utf8_lossy(&entry.path).into_owned());
+                if workdir.join(&path).exists() {
+                    index.add_path(&path)?;
+                } else {
+                    let _ = index.remove_path(&path);
+                }
+            }
+        }
+        index.write()?;
+    }
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let head = repo.head()?.peel_to_commit()?;
+    let theirs = repo.find_commit(upstream.id())?;
+    let signature = signature(repo)?;
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &format!("Merge origin/{}", settings.branch),
+        &tree,
+        &[&head, &theirs],
+    )?;
+    repo.cleanup_state()?;
+    Ok(conflicts)
+}
+
+fn rebase_onto(repo: &Repository, upstream: &AnnotatedCommit) -> Result<bool, git2::Error> {
+    let mut rebase = repo.rebase(None, Some(upstream), None, None)?;
+    let signature = signature(repo)?;
+    while let Some(operation) = rebase.next() {
+        operation?;
+        if repo.index()?.has_conflicts() {
+            rebase.abort()?;
+            return Ok(false);
+        }
+        if let Err(e) = rebase.commit(None, &signature, None)
+            && e.code() != ErrorCode::Applied
+        {
+            rebase.abort()?;
+            return Err(e);
+        }
+    }
+    rebase.finish(Some(&signature))?;
+    Ok(true)
+}
+
+fn integrate(
+    repo: &Repository,
+    workdir: &Path,
+    settings: &SyncSettings,
+) -> Result<(String, Vec<SyncConflict>), git2::Error> {
+    let Ok(reference) = repo.find_reference(&upstream_ref(&settings.branch)) else {
+        return Ok(("up-to-date".to_string(), Vec::new()));
+    };
+    let upstream = repo.reference_to_annotated_commit(&reference)?;
+    let (analysis, _) = repo.merge_analysis(&[&upstream])?;
+    let local = format!("refs/heads/{}", settings.branch);
+    if analysis.is_up_to_date() {
+        return Ok(("up-to-date".to_string(), Vec::new()));
+    }
+    if analysis.is_fast_forward() || analysis.is_unborn() {
+        match repo.find_reference(&local) {
+            Ok(mut r) => {
+                r.set_target(upstream.id(), "fast-forward")?;
+            }
+            Err(_) => {
+                repo.reference(&local, upstream.id(), true, "fast-forward")?;
+            }
+        }
+        repo.set_head(&local)?;
+        repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+        return Ok(("fast-forward".to_string(), Vec::new()));
+    }
+    if settings.strategy == "rebase" && rebase_onto(repo, &upstream)? {
+        return Ok(("rebase".to_string(), Vec::new()));
+    }
+    let conflicts = merge_upstream(repo, workdir, &upstream, settings)?;
+    Ok(("merge".to_string(), conflicts))
+}
+
+fn push(repo: &Repository, branch: &str) -> Result<(), git2::Error> {
+    let rejected = RefCell::new(None);
+    {
+        let mut callbacks = callbacks();
+        callbacks.push_update_reference(|_, status| {
+            if let Some(status) = status {
+                *rejected.borrow_mut() = Some(status.to_string());
+            }
+            Ok(())
+        });
+        let mut options = PushOptions::new();
+        options.remote_callbacks(callbacks);
+        let refspec = format!("refs/heads/{0}:refs/heads/{0}", branch);
+        repo.find_remote("origin")?
+            .push(&[refspec.as_str()], Some(&mut options))?;
+    }
+    match rejected.into_inner() {
+        Some(status) => Err(git2::Error::from_str(&status)),
+        None => Ok(()),
+    }
+}
+
+fn record_conflicts(workdir: &Path, found: &[SyncConflict]) {
+    let mut all = LAST_CONFLICTS.lock().unwrap();
+    let conflicts = all.entry(workdir.to_path_buf()).or_default();
+    conflicts.retain(|c| found.iter().all(|f| f.path != c.path));
+    conflicts.extend(found.iter().cloned());
+}
+
+fn active_conflicts(workdir: &Path) -> Vec<SyncConflict> {
+    let mut all = LAST_CONFLICTS.lock().unwrap();
+    let Some(conflicts) = all.get_mut(workdir) else {
+        return Vec::new();
+    };
+    conflicts.retain(|c| match &c.copy {
+        Some(copy) => workdir.join(copy).exists(),
+        None => std::fs::read_to_string(workdir.join(&c.path))
+            .map(|content| content.contains("<<<<<<< "))
+            .unwrap_or(false),
+    });
+    conflicts.clone()
+}
+
+fn status(workdir: &Path) -> Result<SyncStatus, git2::Error> {
+    let conflicts = active_conflicts(workdir);
+    let Ok(repo) = open_repo(workdir) else {
+        return Ok(SyncStatus {
+            configured: false,
+            remote: None,
+            branch: String::new(),
+            ahead: 0,
+            behind: 0,
+            changed: Vec::new(),
+            conflicts,
+        });
+    };
+    let settings = sync_settings(&repo);
+    let remote = repo
+        .find_remote("origin")
+        .ok()
+        .and_then(|r| r.url().map(String::from));
+    let local = repo.head().ok().and_then(|h| h.target());
+    let upstream = repo
+        .find_reference(&upstream_ref(&settings.branch))
+        .ok()
+        .and_then(|r| r.target());
+    let (ahead, behind) = match (local, upstream) {
+        (Some(local), Some(upstream)) => repo.graph_ahead_behind(local, upstream)?,
+        _ => (0, 0),
+    };
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let changed = repo
+        .statuses(Some(&mut options))?
+        .iter()
+        .filter_map(|s| s.path().map(String::from))
+        .collect();
+    Ok(SyncStatus {
+        configured: remote.is_some(),
+        remote,
+        branch: settings.branch,
+        ahead,
+        behind,
+        changed,
+        conflicts,
+    })
+}
+
+fn run_sync(workdir: &Path) -> Result<SyncReport, git2::Error> {
+    let repo = open_repo(workdir)?;
+    let settings = sync_settings(&repo);
+    let before = repo.head().ok().and_then(|h| h.target());
+    let committed = commit_all(&repo, "Sync vault")?;
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(callbacks());
+    repo.find_remote("origin")?
+        .fetch(&[] as &[&str], Some(&mut options), None)?;
+    let (integration, conflicts) = integrate(&repo, workdir, &settings)?;
+    let after = repo.head()?.target().unwrap_or(Oid::zero());
+    let updated = match integration.as_str() {
+        "up-to-date" => Vec::new(),
+        _ => changed_files(&repo, before, after)?,
+    };
+    record_conflicts(workdir, &conflicts);
+    // Merged notes with conflict markers are committed locally; they only go out once
+    // the markers are gone, so the other machines never see them.
+    let unresolved = active_conflicts(workdir).iter().any(|c| c.copy.is_none());
+    if !unresolved {
+        push(&repo, &settings.branch)?;
+    }
+    Ok(SyncReport {
+        committed,
+        integration,
+        pushed: !unresolved,
+        updated,
+        conflicts,
+        status: status(workdir)?,
+    })
+}
+
+#[tauri::command]
+pub async fn configure_sync(
+    remote: String,
+    branch: Option<String>,
+    strategy: Option<String>,
+    conflicts: Option<String>,
+) -> Result<(), String> {
+    let workdir = workdir().await?;
+    let repo = match Repository::open(&workdir) {
+        Ok(repo) => repo,
+        Err(_) => Repository::init_opts(
+            &workdir,
+            RepositoryInitOptions::new().initial_head(branch.as_deref().unwrap_or("main")),
+        )
+        .map_err(|e| e.to_string())?,
+    };
+    match repo.find_remote("origin") {
+        Ok(_) => repo.remote_set_url("origin", &remote),
+        Err(_) => repo.remote("origin", &remote).map(|_| ()),
+    }
+    .map_err(|e| e.to_string())?;
+    let mut config = repo.config().map_err(|e| e.to_string())?;
+    let values = [
+        ("void.sync.branch", branch),
+        ("void.sync.strategy", strategy),
+        ("void.sync.conflicts", conflicts),
+    ];
+    for (key, value) in values {
+        if let Some(value) = value {
+            config.set_str(key, &value).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_sync_status() -> Result<SyncStatus, String> {
+    let workdir = workdir().await?;
+    status(&workdir).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_sync_conflicts() -> Result<Vec<SyncConflict>, String> {
+    Ok(active_conflicts(&workdir().await?))
+}
+
+#[tauri::command]
+pub async fn sync_vault(app: tauri::AppHandle) -> Result<SyncReport, String> {
+    let workdir = workdir().await?;
+    let root = workdir.clone();
+    let report = tauri::async_runtime::spawn_blocking(move || run_sync(&root))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            let response = e.to_string();
+            app.emit("error", &response).unwrap();
+            response
+        })?;
+    for path in &report.updated {
+        entry_updated(&workdir, path).await;
+    }
+    for conflict in report.conflicts.iter().filter_map(|c| c.copy.as_ref()) {
+        entry_updated(&workdir, conflict).await;
+    }
+    app.emit("sync-status", &report.status).unwrap();
+    if !report.conflicts.is_empty() {
+        app.emit("sync-conflicts", &report.conflicts).unwrap();
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("void-sync-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn vault(root: &Path, name: &str, remote: &str) -> PathBuf {
+        let workdir = root.join(name);
+        let repo =
+            Repository::init_opts(&workdir, RepositoryInitOptions::new().initial_head("main"))
+                .unwrap();
+        repo.remote("origin", remote).unwrap();
+        workdir
+    }
+
+    fn write(workdir: &Path, path: &str, content: &str) {
+        let path = workdir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn remote_files(remote: &Path) -> Vec<String> {
+        let repo = Repository::open_bare(remote).unwrap();
+        let tree = repo
+            .revparse_single("main")
+            .unwrap()
+            .peel_to_tree()
+            .unwrap();
+        let mut files = Vec::new();
+        tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() == Some(git2::ObjectType::Blob) {
+                files.push(format!("{}{}", dir, entry.name().unwrap()));
+            }
+            git2::TreeWalkResult::Ok
+        })
+        .unwrap();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn sync_skips_trash_and_ignored_paths() {
+        let root = scratch("ignore");
+        let remote = root.join("remote.git");
+        Repository::init_bare(&remote).unwrap();
+        let url = format!("file://{}", remote.to_string_lossy());
+        let a = vault(&root, "a", &url);
+        write(&a, "note.md", "# Note");
+        write(&a, ".voidignore", "private/\n");
+        write(&a, "private/secret.md", "secret");
+        write(&a, ".trash/1.md", "deleted");
+        write(&a, ".trash/1.md.meta.json", "{}");
+        write(&a, ".conf/vault.json", "{}");
+
+        let report = run_sync(&a).unwrap();
+        assert!(report.committed && report.pushed);
+        assert!(report.status.changed.is_empty());
+        assert_eq!(
+            remote_files(&remote),
+            [".conf/vault.json", ".voidignore", "note.md"]
+        );
+
+        let b = vault(&root, "b", &url);
+        let report = run_sync(&b).unwrap();
+        assert_eq!(report.integration, "fast-forward");
+        assert_eq!(
+            std::fs::read_to_string(b.join("note.md")).unwrap(),
+            "# Note"
+        );
+        write(&b, "other.md", "from b");
+        run_sync(&b).unwrap();
+        run_sync(&a).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(a.join("other.md")).unwrap(),
+            "from b"
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn sync_holds_back_conflict_markers() {
+        let root = scratch("markers");
+        let remote = root.join("remote.git");
+        Repository::init_bare(&remote).unwrap();
+        let url = format!("file://{}", remote.to_string_lossy());
+        let a = vault(&root, "a", &url);
+        write(&a, "note.md", "line\n");
+        run_sync(&a).unwrap();
+        let b = vault(&root, "b", &url);
+        run_sync(&b).unwrap();
+        write(&a, "note.md", "from a\n");
+        run_sync(&a).unwrap();
+        write(&b, "note.md", "from b\n");
+
+        let report = run_sync(&b).unwrap();
+        assert!(!report.pushed);
+        assert_eq!(report.conflicts.len(), 1);
+        let report = run_sync(&b).unwrap();
+        assert!(!report.pushed);
+        assert_eq!(active_conflicts(&b).len(), 1);
+        assert!(active_conflicts(&a).is_empty());
+        let remote_note = Repository::open_bare(&remote)
+            .unwrap()
+            .revparse_single("main:note.md")
+            .unwrap()
+            .peel_to_blob()
+            .unwrap()
+            .content()
+            .to_vec();
+        assert_eq!(remote_note, b"from a\n");
+
+        write(&b, "note.md", "from both\n");
+        let report = run_sync(&b).unwrap();
+        assert!(report.pushed);
+        assert!(active_conflicts(&b).is_empty());
+        run_sync(&a).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(a.join("note.md")).unwrap(),
+            "from both\n"
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -15,7 +15,10 @@ use crate::MAIN_FOLDER_PREFIX;
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use super::{DB, EntityControl, EntityError, ThemeRepo, ThemeRepoField, add_repo};
+use super::{
+    DB, Entity, EntityError, EntityVersion, ListQuery, ThemeRepo, ThemeRepoFields, add_repo,
+    now_millis,
+};
 use serde::Deserialize;
 use std::{fs, vec};
 use tauri::Emitter;
@@ -46,33 +49,23 @@ pub async fn get_theme(name: String, _app: tauri::AppHandle) -> Result<String, S
 }
 
 #[tauri::command]
-pub async fn get_list_of_themes(key: String) -> Result<Vec<ThemeRepo>, String> {
-    let db = DB.get().unwrap();
-    let entities = db
-        .get_all_members::<ThemeRepo>("themes_repo")
+pub async fn get_list_of_themes(
+    key: String,
+    query: Option<ListQuery>,
+) -> Result<Vec<ThemeRepo>, String> {
+    let installed = match key.as_str() {
+        "installed" => true,
+        "not_installed" => false,
+        _ => return Ok(Vec::new()),
+    };
+    DB.get()
+        .unwrap()
+        .query::<ThemeRepo>()
+        .filter(ThemeRepoFields::IsInstalled(installed))
+        .apply(query.unwrap_or_default())
+        .fetch()
         .await
-        .map_err(|e| e.to_string())?;
-    match key.as_str() {
-        "installed" => Ok(entities
-            .into_iter()
-            .filter(move |t| {
-                t.get_value_by_key("installed".to_string())
-                    .unwrap()
-                    .as_str()
-                    == "true"
-            })
-            .collect::<Vec<ThemeRepo>>()),
-        "not_installed" => Ok(entities
-            .into_iter()
-            .filter(|t| {
-                t.get_value_by_key("installed".to_string())
-                    .unwrap()
-                    .as_str()
-                    == "false"
-            })
-            .collect::<Vec<ThemeRepo>>()),
-        _ => Ok(Vec::<ThemeRepo>::new()),
-    }
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -101,14 +94,15 @@ pub async fn create_themes_table(link: String, app: tauri::AppHandle) {
             linkparts.get(2).unwrap(),
             theme.name
         );
-        let input: Vec<ThemeRepoField> = vec![
-            ThemeRepoField::Name(theme.name.clone()),
-            ThemeRepoField::Author(theme.author.clone()),
-            ThemeRepoField::Version(theme.version.clone()),
-            ThemeRepoField::Link(link),
-            ThemeRepoField::Installed("false".to_string()),
+        let link = url::Url::parse(&link).unwrap();
+        let input: Vec<ThemeRepoFields> = vec![
+            ThemeRepoFields::ThemeName(theme.name.clone()),
+            ThemeRepoFields::ThemeAuthor(theme.author.clone()),
+            ThemeRepoFields::ThemeVersion(EntityVersion::from(theme.version.as_str())),
+            ThemeRepoFields::ThemeLink(link),
+            ThemeRepoFields::IsInstalled(false),
         ];
-        db.create::<ThemeRepoField, ThemeRepo>(input, app.clone(), "themes_repo", &theme.name)
+        db.create::<ThemeRepoFields, ThemeRepo>(input, app.clone(), ThemeRepo::TABLE, &theme.name)
             .await
             .unwrap();
     }
@@ -117,34 +111,27 @@ pub async fn create_themes_table(link: String, app: tauri::AppHandle) {
 #[tauri::command]
 pub async fn clone_theme(key: String, app: tauri::AppHandle) -> Result<(), String> {
     let db = DB.get().unwrap();
-    let themes_list = db
-        .get_all_members::<ThemeRepo>("themes_repo")
+    let selected_theme = db
+        .query::<ThemeRepo>()
+        .filter(ThemeRepoFields::ThemeName(key.clone()))
+        .first()
         .await
         .map_err(|e| e.to_string())?;
-    let mut selected_theme: Option<ThemeRepo> = None;
-    for theme in themes_list {
-        if theme
-            .get_value_by_key("name".to_string())
-            .map_err(|e| e.to_string())?
-            == key
-        {
-            selected_theme = Some(theme);
-        }
-    }
     match selected_theme {
         Some(theme) => {
-            let db = DB.get().unwrap();
             let client = reqwest::Client::new();
             let theme_css = client
-                .get(theme.get_value_by_key("link".to_string()).unwrap())
+                .get(theme.theme_link)
                 .send()
                 .await
                 .map_err(|e| e.to_string())?;
+            db.update(&key, ThemeRepo::TABLE, ThemeRepoFields::IsInstalled(true))
+                .await
+                .map_err(|e| e.to_string())?;
             db.update(
-                key.clone(),
-                "themes_repo",
-                "is_installed".to_string(),
-                "true".to_string(),
+                &key,
+                ThemeRepo::TABLE,
+                ThemeRepoFields::InstalledAt(Some(now_millis())),
             )
             .await
             .map_err(|e| e.to_string())?;
@@ -168,12 +155,12 @@ pub async fn clone_theme(key: String, app: tauri::AppHandle) -> Result<(), Strin
 pub async fn check_theme_update(theme_name: String, app: tauri::AppHandle) -> Result<(), String> {
     let db = DB.get().unwrap();
     let theme = db
-        .get::<ThemeRepo>(&theme_name, "themes_repo")
+        .get::<ThemeRepo>(&theme_name, ThemeRepo::TABLE)
         .await
         .map_err(|e| e.to_string())?;
     let fetch_client = reqwest::Client::new();
     let css = fetch_client
-        .get(theme.get_value_by_key("link".to_string()).unwrap())
+        .get(theme.theme_link)
         .send()
         .await
         .unwrap()
@@ -206,10 +193,16 @@ pub async fn delete_theme(theme_name: String, app: tauri::AppHandle) -> Result<(
         .join(&theme_name);
     std::fs::remove_dir_all(theme_dir).map_err(|e| e.to_string())?;
     db.update(
-        theme_name,
-        "themes_repo",
-        "is_installed".to_string(),
-        "false".to_string(),
+        &theme_name,
+        ThemeRepo::TABLE,
+        ThemeRepoFields::IsInstalled(false),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    db.update(
+        &theme_name,
+        ThemeRepo::TABLE,
+        ThemeRepoFields::InstalledAt(None),
     )
     .await
     .map_err(|e| e.to_string())?;
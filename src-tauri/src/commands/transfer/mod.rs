@@ -0,0 +1,193 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+const PROGRESS_EVENT: &str = "fs-progress";
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    #[default]
+    Fail,
+    Skip,
+    Overwrite,
+    KeepBoth,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct TransferProgress {
+    pub operation: String,
+    pub path: String,
+    pub files_done: u64,
+    pub files_total: u64,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+pub struct ProgressReporter {
+    app: Option<tauri::AppHandle>,
+    state: TransferProgress,
+    last: Option<Instant>,
+}
+
+impl ProgressReporter {
+    pub fn new(app: tauri::AppHandle, operation: &str, path: &str) -> Self {
+        ProgressReporter {
+            app: Some(app),
+            state: TransferProgress {
+                operation: operation.to_string(),
+                path: path.to_string(),
+                ..Default::default()
+            },
+            last: None,
+        }
+    }
+
+    pub fn silent() -> Self {
+        ProgressReporter {
+            app: None,
+            state: TransferProgress::default(),
+            last: None,
+        }
+    }
+
+    fn measure(&mut self, source: &Path) {
+        if self.app.is_none() {
+            return;
+        }
+        for entry in jwalk::WalkDir::new(source)
+            .skip_hidden(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            self.state.files_total += 1;
+            self.state.bytes_total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+
+    fn advance(&mut self, bytes: u64) {
+        self.state.files_done += 1;
+        self.state.bytes_done += bytes;
+        if self
+            .last
+            .is_none_or(|last| last.elapsed() >= PROGRESS_INTERVAL)
+        {
+            self.emit();
+        }
+    }
+
+    fn emit(&mut self) {
+        if let Some(app) = &self.app {
+            let _ = app.emit(PROGRESS_EVENT, &self.state);
+            self.last = Some(Instant::now());
+        }
+    }
+}
+
+fn copy_recursive(source: &Path, target: &Path, progress: &mut ProgressReporter) -> io::Result<()> {
+    let meta = fs::symlink_metadata(source)?;
+    if meta.file_type().is_symlink() {
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(fs::read_link(source)?, target)?;
+        return Ok(());
+    }
+    if meta.is_dir() {
+        fs::create_dir(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &target.join(entry.file_name()), progress)?;
+        }
+        fs::set_permissions(target, meta.permissions())?;
+    } else {
+        fs::copy(source, target)?;
+        progress.advance(meta.len());
+    }
+    Ok(())
+}
+
+fn remove_any(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path)?.is_dir() {
+        true => fs::remove_dir_all(path),
+        false => fs::remove_file(path),
+    }
+}
+
+// `target` must not exist; a partially copied tree is removed on failure.
+pub fn copy_tree(source: &Path, target: &Path, progress: &mut ProgressReporter) -> io::Result<()> {
+    if fs::symlink_metadata(target).is_ok() {
+        return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+    }
+    progress.measure(source);
+    let result = copy_recursive(source, target, progress);
+    if result.is_err() {
+        let _ = remove_any(target);
+    }
+    progress.emit();
+    result
+}
+
+// Renames when possible and falls back to copy-then-delete across devices.
+pub fn move_tree(source: &Path, target: &Path, progress: &mut ProgressReporter) -> io::Result<()> {
+    use rustix::fs::{CWD, RenameFlags, renameat_with};
+    match renameat_with(CWD, source, CWD, target, RenameFlags::NOREPLACE) {
+        Ok(()) => Ok(()),
+        Err(rustix::io::Errno::XDEV) => {
+            copy_tree(source, target, progress)?;
+            remove_any(source)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn run_blocking<F>(
+    source: PathBuf,
+    target: PathBuf,
+    mut progress: ProgressReporter,
+    f: F,
+) -> Result<(), String>
+where
+    F: FnOnce(&Path, &Path, &mut ProgressReporter) -> io::Result<()> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(move || f(&source, &target, &mut progress))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+pub async fn copy_path(
+    source: PathBuf,
+    target: PathBuf,
+    progress: ProgressReporter,
+) -> Result<(), String> {
+    run_blocking(source, target, progress, copy_tree).await
+}
+
+pub async fn move_path(
+    source: PathBuf,
+    target: PathBuf,
+    progress: ProgressReporter,
+) -> Result<(), String> {
+    run_blocking(source, target, progress, move_tree).await
+}
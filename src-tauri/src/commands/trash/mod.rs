@@ -0,0 +1,257 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    FsAction, Link, PathError, ProgressReporter, apply_link_rewrites, entry_removed, entry_updated,
+    incoming_links, is_under, move_tree, now_millis, plan_restored_links, record_fs_op, resolve_in,
+    sibling_path, workdir, write_atomic,
+};
+
+const TRASH_DIR: &str = ".trash";
+const DEFAULT_RETENTION_DAYS: u64 = 30;
+const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TrashItem {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub kind: String,
+    pub deleted_at: u64,
+    pub links: Vec<Link>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TrashConfig {
+    retention_days: u64,
+}
+
+fn trash_dir(workdir: &Path) -> PathBuf {
+    workdir.join(TRASH_DIR)
+}
+
+fn read_config(workdir: &Path) -> TrashConfig {
+    std::fs::read(trash_dir(workdir).join("config.json"))
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or(TrashConfig {
+            retention_days: DEFAULT_RETENTION_DAYS,
+        })
+}
+
+fn trash_items(workdir: &Path) -> Vec<TrashItem> {
+    let Ok(entries) = std::fs::read_dir(trash_dir(workdir)) else {
+        return Vec::new();
+    };
+    // The id names the trashed entry inside `.trash`, so it has to match the metadata file.
+    let mut items = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            let id = name.strip_suffix(".meta.json")?.to_string();
+            let item = serde_json::from_slice::<TrashItem>(&std::fs::read(e.path()).ok()?).ok()?;
+            (item.id == id).then_some(item)
+        })
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    items
+}
+
+fn erase_item(workdir: &Path, id: &str) -> Result<(), String> {
+    let item = trash_dir(workdir).join(id);
+    let result = match item.is_dir() {
+        true => std::fs::remove_dir_all(&item),
+        false => std::fs::remove_file(&item),
+    };
+    match result {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.to_string()),
+        _ => {}
+    }
+    let _ = std::fs::remove_file(trash_dir(workdir).join(format!("{}.meta.json", id)));
+    Ok(())
+}
+
+fn purge_expired(workdir: &Path) {
+    let retention = read_config(workdir).retention_days;
+    if retention == 0 {
+        return;
+    }
+    let cutoff = now_millis().saturating_sub(retention.saturating_mul(DAY_MILLIS));
+    for item in trash_items(workdir) {
+        if item.deleted_at < cutoff {
+            let _ = erase_item(workdir, &item.id);
+        }
+    }
+}
+
+fn move_to_trash(workdir: &Path, path: &str, links: Vec<Link>) -> Result<TrashItem, String> {
+    let source = workdir.join(path);
+    let kind = match source.is_dir() {
+        true => "folder",
+        false => "file",
+    };
+    let dir = trash_dir(workdir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let deleted_at = now_millis();
+    let mut id = deleted_at.to_string();
+    let mut n = 1;
+    while dir.join(&id).exists() || dir.join(format!("{}.meta.json", id)).exists() {
+        id = format!("{}-{}", deleted_at, n);
+        n += 1;
+    }
+    let item = TrashItem {
+        name: path.rsplit('/').next().unwrap_or(path).to_string(),
+        id,
+        path: path.to_string(),
+        kind: kind.to_string(),
+        deleted_at,
+        links,
+    };
+    let meta = dir.join(format!("{}.meta.json", item.id));
+    let data = serde_json::to_vec_pretty(&item).map_err(|e| e.to_string())?;
+    write_atomic(&meta, &data).map_err(|e| e.to_string())?;
+    move_tree(
+        &source,
+        &dir.join(&item.id),
+        &mut ProgressReporter::silent(),
+    )
+    .map_err(|e| {
+        let _ = std::fs::remove_file(&meta);
+        e.to_string()
+    })?;
+    Ok(item)
+}
+
+// Metadata is a plain file in the vault and may come from another machine through sync,
+// so the recorded path goes through the vault resolver like any other input.
+fn original_path(workdir: &Path, item: &TrashItem) -> Result<String, PathError> {
+    let path = resolve_in(workdir, &item.path)?.relative;
+    match path.is_empty() || is_under(&path, TRASH_DIR) {
+        true => Err(PathError::OutsideVault(item.path.clone())),
+        false => Ok(path),
+    }
+}
+
+// Restores next to the original when its place has been taken since.
+fn restore_target(workdir: &Path, original: &str, item: &TrashItem) -> String {
+    match workdir.join(original).symlink_metadata() {
+        Ok(_) => sibling_path(workdir, original, item.kind == "folder", "restored"),
+        Err(_) => original.to_string(),
+    }
+}
+
+pub async fn trash_entry(workdir: &Path, path: &str) -> Result<TrashItem, String> {
+    let links = incoming_links(workdir, path);
+    let item = move_to_trash(workdir, path, links)?;
+    entry_removed(workdir, path).await;
+    Ok(item)
+}
+
+pub async fn restore_trash_item(workdir: &Path, id: &str) -> Result<String, String> {
+    let item = trash_items(workdir)
+        .into_iter()
+        .find(|item| item.id == id)
+        .ok_or_else(|| "элемент не найден в корзине".to_string())?;
+    let original = original_path(workdir, &item)?;
+    let target = restore_target(workdir, &original, &item);
+    let destination = workdir.join(&target);
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    move_tree(
+        &trash_dir(workdir).join(&item.id),
+        &destination,
+        &mut ProgressReporter::silent(),
+    )
+    .map_err(|e| e.to_string())?;
+    let _ = std::fs::remove_file(trash_dir(workdir).join(format!("{}.meta.json", item.id)));
+    entry_updated(workdir, &target).await;
+    let links = item
+        .links
+        .into_iter()
+        .filter(|l| resolve_in(workdir, &l.source).is_ok_and(|p| p.relative == l.source))
+        .collect::<Vec<_>>();
+    let rewrites = plan_restored_links(workdir, &links, &original, &target);
+    let changed = apply_link_rewrites(workdir, &rewrites)?;
+    let mut sources = links
+        .iter()
+        .map(|l| l.source.clone())
+        .chain(changed)
+        .collect::<Vec<_>>();
+    sources.sort();
+    sources.dedup();
+    for source in sources {
+        entry_updated(workdir, &source).await;
+    }
+    Ok(target)
+}
+
+pub async fn sweep_trash() -> Result<(), String> {
+    let workdir = workdir().await?;
+    purge_expired(&workdir);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_trash() -> Result<Vec<TrashItem>, String> {
+    let workdir = workdir().await?;
+    purge_expired(&workdir);
+    Ok(trash_items(&workdir))
+}
+
+#[tauri::command]
+pub async fn restore_from_trash(id: String) -> Result<String, String> {
+    let workdir = workdir().await?;
+    let target = restore_trash_item(&workdir, &id).await?;
+    record_fs_op(
+        &workdir,
+        "restore",
+        vec![FsAction::Trash {
+            path: target.clone(),
+        }],
+    );
+    Ok(target)
+}
+
+#[tauri::command]
+pub async fn empty_trash() -> Result<(), String> {
+    let workdir = workdir().await?;
+    for item in trash_items(&workdir) {
+        erase_item(&workdir, &item.id)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_trash_retention() -> Result<u64, String> {
+    Ok(read_config(&workdir().await?).retention_days)
+}
+
+#[tauri::command]
+pub async fn set_trash_retention(days: u64) -> Result<(), String> {
+    let workdir = workdir().await?;
+    std::fs::create_dir_all(trash_dir(&workdir)).map_err(|e| e.to_string())?;
+    let data = serde_json::to_vec_pretty(&TrashConfig {
+        retention_days: days,
+    })
+    .map_err(|e| e.to_string())?;
+    write_atomic(&trash_dir(&workdir).join("config.json"), &data).map_err(|e| e.to_string())?;
+    purge_expired(&workdir);
+    Ok(())
+}
@@ -0,0 +1,109 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::Emitter;
+
+use super::{PluginList, workdir, write_atomic};
+
+// Lives inside the vault so it is synced and shared together with the notes.
+pub const VAULT_CONFIG_FILE: &str = ".conf/vault.json";
+
+// What a vault changes on top of the settings and plugins kept in the database.
+// The active theme is the `appearance.theme` setting.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct VaultConfig {
+    #[serde(default)]
+    pub settings: Map<String, Value>,
+    #[serde(default)]
+    pub plugins: BTreeMap<String, bool>,
+}
+
+fn read_config(workdir: &Path) -> Result<VaultConfig, String> {
+    let path = workdir.join(VAULT_CONFIG_FILE);
+    match std::fs::read(&path) {
+        Ok(content) => {
+            serde_json::from_slice(&content).map_err(|e| format!("{}: {}", path.display(), e))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(VaultConfig::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn write_config(workdir: &Path, config: &VaultConfig) -> Result<(), String> {
+    let path = workdir.join(VAULT_CONFIG_FILE);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_vec_pretty(config).map_err(|e| e.to_string())?;
+    write_atomic(&path, &content).map_err(|e| e.to_string())
+}
+
+// Without an open vault there is nothing to apply.
+pub async fn vault_config() -> Result<VaultConfig, String> {
+    match workdir().await {
+        Ok(dir) => read_config(&dir),
+        Err(_) => Ok(VaultConfig::default()),
+    }
+}
+
+pub async fn update_vault_config(
+    change: impl FnOnce(&mut VaultConfig),
+    app: &tauri::AppHandle,
+) -> Result<VaultConfig, String> {
+    let workdir = workdir().await?;
+    let mut config = read_config(&workdir)?;
+    change(&mut config);
+    write_config(&workdir, &config)?;
+    let _ = app.emit("config-changed", &config);
+    Ok(config)
+}
+
+// Plugins the vault turns on or off report the vault's choice.
+pub async fn apply_vault_plugins(plugins: &mut [PluginList]) {
+    let config = vault_config().await.unwrap_or_default();
+    for plugin in plugins {
+        if let Some(enabled) = config.plugins.get(&plugin.plugin_name) {
+            plugin.is_enabled = *enabled;
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_vault_config() -> Result<VaultConfig, String> {
+    vault_config().await
+}
+
+// A missing `enabled` goes back to the global state of the plugin.
+#[tauri::command]
+pub async fn set_vault_plugin(
+    name: String,
+    enabled: Option<bool>,
+    app: tauri::AppHandle,
+) -> Result<VaultConfig, String> {
+    update_vault_config(
+        |config| {
+            match enabled {
+                Some(enabled) => config.plugins.insert(name, enabled),
+                None => config.plugins.remove(&name),
+            };
+        },
+        &app,
+    )
+    .await
+}
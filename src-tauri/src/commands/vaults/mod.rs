@@ -0,0 +1,185 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_fs::FsExt;
+
+use super::{
+    DB, Entity, EntityError, Order, Vault, VaultFields, get_env, now_millis, refresh_global_ignore,
+    reindex_vault, set_env, sweep_trash, sync_search_index, vault_root, watch_vault,
+};
+
+// The vault the fs scope currently allows, revoked when another one is opened.
+static SCOPED_VAULT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// Vault records are keyed by `vault_root`, so `/a/vault` and `/a/vault/` are the same vault.
+fn vault_key(path: &str) -> String {
+    vault_root(path).to_string_lossy().to_string()
+}
+
+async fn find_vault(path: &str) -> Result<Option<Vault>, String> {
+    DB.get()
+        .unwrap()
+        .database
+        .select((Vault::TABLE, path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn save_vault(vault: &Vault) -> Result<(), String> {
+    DB.get()
+        .unwrap()
+        .database
+        .upsert::<Option<Vault>>((Vault::TABLE, vault.path.as_str()))
+        .content(vault.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+async fn registered_vault(path: &str) -> Result<Vault, String> {
+    find_vault(path)
+        .await?
+        .ok_or(EntityError::NotFound.to_string())
+}
+
+fn folder_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+async fn register_vault(
+    app: &AppHandle,
+    name: Option<String>,
+    path: &str,
+) -> Result<Vault, String> {
+    let path = vault_key(path);
+    let path = path.as_str();
+    if let Some(vault) = find_vault(path).await? {
+        return Ok(vault);
+    }
+    let input = vec![
+        VaultFields::Name(name.unwrap_or_else(|| folder_name(path))),
+        VaultFields::Path(path.to_string()),
+        VaultFields::LastOpened(0),
+    ];
+    DB.get()
+        .unwrap()
+        .create::<VaultFields, Vault>(input, app.clone(), Vault::TABLE, path)
+        .await
+        .map_err(|e| e.to_string())?;
+    registered_vault(path).await
+}
+
+// Scopes the filesystem, the watcher and the background indexing to `workdir`
+// and records it as the most recently opened vault. Tauri has no way to take back an
+// allowed directory, so the previous vault is forbidden instead.
+pub async fn activate_vault(app: &AppHandle, workdir: &str) -> Result<Vault, String> {
+    let workdir = vault_key(workdir);
+    let workdir = workdir.as_str();
+    {
+        let mut scoped = SCOPED_VAULT.lock().unwrap();
+        if let Some(previous) = scoped.take().filter(|p| p != Path::new(workdir)) {
+            let _ = app.fs_scope().forbid_directory(previous, true);
+        }
+        let _ = app.fs_scope().allow_directory(workdir, true);
+        *scoped = Some(PathBuf::from(workdir));
+    }
+    // The vault may pin its own global ignore patterns.
+    refresh_global_ignore().await;
+    watch_vault(app.clone(), PathBuf::from(workdir))?;
+    let mut vault = register_vault(app, None, workdir).await?;
+    vault.last_opened = now_millis();
+    save_vault(&vault).await?;
+    tauri::async_runtime::spawn(async {
+        if let Err(e) = sync_search_index().await {
+            eprintln!("Ошибка при индексации{}", e);
+        }
+        if let Err(e) = sweep_trash().await {
+            eprintln!("Ошибка при очистке корзины{}", e);
+        }
+    });
+    Ok(vault)
+}
+
+#[tauri::command]
+pub async fn list_vaults(app: AppHandle) -> Result<Vec<Vault>, String> {
+    let workdir = get_env("workdir".to_string()).await?;
+    if !workdir.is_empty() {
+        register_vault(&app, None, &workdir).await?;
+    }
+    DB.get()
+        .unwrap()
+        .query::<Vault>()
+        .order_by("last_opened", Order::Desc)
+        .fetch()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_vault(
+    path: String,
+    name: Option<String>,
+    app: AppHandle,
+) -> Result<Vault, String> {
+    if !Path::new(&path).is_dir() {
+        return Err(format!("Папка `{}` не найдена", path));
+    }
+    register_vault(&app, name, &path).await
+}
+
+#[tauri::command]
+pub async fn remove_vault(path: String) -> Result<(), String> {
+    let path = vault_key(&path);
+    if vault_key(&get_env("workdir".to_string()).await?) == path {
+        return Err("Нельзя удалить открытое хранилище".to_string());
+    }
+    DB.get()
+        .unwrap()
+        .database
+        .delete::<Option<Vault>>((Vault::TABLE, path.as_str()))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn open_vault(path: String, app: AppHandle) -> Result<Vault, String> {
+    let vault = registered_vault(&vault_key(&path)).await?;
+    if !Path::new(&vault.path).is_dir() {
+        return Err(format!("Папка `{}` не найдена", vault.path));
+    }
+    set_env("workdir".to_string(), vault.path.clone()).await?;
+    reindex_vault().await;
+    let vault = activate_vault(&app, &vault.path).await?;
+    let _ = app.emit("vault-opened", &vault);
+    Ok(vault)
+}
+
+#[tauri::command]
+pub async fn rename_vault(path: String, name: String) -> Result<Vault, String> {
+    let mut vault = registered_vault(&vault_key(&path)).await?;
+    vault.name = name;
+    save_vault(&vault).await?;
+    Ok(vault)
+}
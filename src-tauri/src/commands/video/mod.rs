@@ -13,6 +13,7 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashSet;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -22,12 +23,15 @@ use warp::Filter;
 
 pub struct VideoState {
     pub video_path: Mutex<Option<PathBuf>>,
+    // Only paths that went through the resolver may be streamed.
+    pub allowed: Mutex<HashSet<PathBuf>>,
 }
 
 impl VideoState {
     pub fn new() -> Self {
         Self {
             video_path: Mutex::new(None),
+            allowed: Mutex::new(HashSet::new()),
         }
     }
 }
@@ -40,17 +44,24 @@ pub fn init_video_server() -> Arc<VideoState> {
 
 #[tauri::command]
 pub async fn check_file_exists(path: String) -> Result<bool, String> {
-    let path = PathBuf::from(path);
+    let path = super::resolve_readable_path(&path).await?;
     Ok(path.exists())
 }
 
 #[tauri::command]
-pub async fn get_video_url(path: String) -> Result<String, String> {
-    let absolute_path = std::fs::canonicalize(&path)
-        .map_err(|e| format!("Failed to get absolute path: {}", e))?
-        .to_string_lossy()
-        .to_string()
-        .replace("\\\\?\\", "");
+pub async fn get_video_url(
+    path: String,
+    state: tauri::State<'_, Arc<VideoState>>,
+) -> Result<String, String> {
+    let path = super::resolve_readable_path(&path).await?;
+    let path =
+        std::fs::canonicalize(&path).map_err(|e| format!("Failed to get absolute path: {}", e))?;
+    let absolute_path = path.to_string_lossy().to_string().replace("\\\\?\\", "");
+    state
+        .allowed
+        .lock()
+        .await
+        .insert(PathBuf::from(&absolute_path));
 
     Ok(format!(
         "http://localhost:1421/video?path={}",
@@ -63,8 +74,10 @@ pub async fn set_video_path(
     path: String,
     state: tauri::State<'_, Arc<VideoState>>,
 ) -> Result<(), String> {
+    let path = super::resolve_readable_path(&path).await?;
+    state.allowed.lock().await.insert(path.clone());
     let mut video_path = state.video_path.lock().await;
-    *video_path = Some(PathBuf::from(path));
+    *video_path = Some(path);
     Ok(())
 }
 
@@ -95,7 +108,7 @@ fn start_video_server(state: Arc<VideoState>) {
                                 let path = state.video_path.lock().await.clone();
                                 path.ok_or(warp::reject::not_found())?
                             };
-                            if !path.exists() {
+                            if !path.exists() || !state.allowed.lock().await.contains(&path) {
                                 return Err(warp::reject::not_found());
                             }
                             let mime_type = if let Some(ext) = path.extension() {
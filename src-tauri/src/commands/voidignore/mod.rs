@@ -0,0 +1,164 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{reindex_vault, setting_value, workdir, write_atomic};
+
+pub const IGNORE_FILE: &str = ".voidignore";
+
+// Internal folders and the ignore file itself; a `!pattern` in `.voidignore` brings them back.
+const DEFAULT_PATTERNS: &[&str] = &[".git/", ".conf/", ".trash/", "/.voidignore"];
+
+static MATCHER: Lazy<RwLock<Option<CachedMatcher>>> = Lazy::new(|| RwLock::new(None));
+
+// Global patterns are the `ignore.patterns` setting. Matching is synchronous, so the value
+// in effect is kept here and replaced by `refresh_global_ignore` whenever it may change.
+static GLOBAL_PATTERNS: Lazy<RwLock<Vec<String>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+pub const IGNORE_SETTING: &str = "ignore.patterns";
+
+struct CachedMatcher {
+    root: PathBuf,
+    stamp: Option<SystemTime>,
+    matcher: Arc<Gitignore>,
+}
+
+#[derive(Serialize)]
+pub struct IgnorePatterns {
+    pub global: Vec<String>,
+    pub vault: Vec<String>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn patterns<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn read_patterns(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .map(|content| patterns(content.lines()))
+        .unwrap_or_default()
+}
+
+fn global_patterns() -> Vec<String> {
+    GLOBAL_PATTERNS.read().unwrap().clone()
+}
+
+// Rereads the setting, e.g. after it was changed or another vault pinned its own value,
+// and reindexes when the patterns differ.
+pub async fn refresh_global_ignore() {
+    let value = setting_value(IGNORE_SETTING).await;
+    let lines = value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str);
+    let patterns = patterns(lines);
+    {
+        let mut global = GLOBAL_PATTERNS.write().unwrap();
+        if *global == patterns {
+            return;
+        }
+        *global = patterns;
+    }
+    *MATCHER.write().unwrap() = None;
+    reindex_vault().await;
+}
+
+fn build_matcher(root: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in DEFAULT_PATTERNS {
+        let _ = builder.add_line(None, pattern);
+    }
+    // Vault rules come last so they can override global ones.
+    for pattern in global_patterns() {
+        let _ = builder.add_line(None, &pattern);
+    }
+    let _ = builder.add(root.join(IGNORE_FILE));
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+pub fn vault_ignore(root: &Path) -> Arc<Gitignore> {
+    let stamp = modified(&root.join(IGNORE_FILE));
+    if let Some(cached) = MATCHER
+        .read()
+        .unwrap()
+        .as_ref()
+        .filter(|cached| cached.root == root && cached.stamp == stamp)
+    {
+        return cached.matcher.clone();
+    }
+    let matcher = Arc::new(build_matcher(root));
+    *MATCHER.write().unwrap() = Some(CachedMatcher {
+        root: root.to_path_buf(),
+        stamp,
+        matcher: matcher.clone(),
+    });
+    matcher
+}
+
+// What vault sync leaves out of commits: the trash and everything ignored globally or
+// by `.voidignore`. Unlike the defaults above `.conf` is kept, so vault settings travel along.
+pub fn sync_ignore_rules(root: &Path) -> String {
+    let mut rules = vec![".trash/".to_string()];
+    rules.extend(global_patterns());
+    rules.extend(read_patterns(&root.join(IGNORE_FILE)));
+    rules.join("\n")
+}
+
+// `path` is relative to `root`; an entry inside an ignored folder is ignored too.
+pub fn is_ignored(root: &Path, path: &str, is_dir: bool) -> bool {
+    !path.is_empty()
+        && vault_ignore(root)
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+}
+
+#[tauri::command]
+pub async fn get_ignore_patterns() -> Result<IgnorePatterns, String> {
+    let workdir = workdir().await?;
+    Ok(IgnorePatterns {
+        global: global_patterns(),
+        vault: read_patterns(&workdir.join(IGNORE_FILE)),
+    })
+}
+
+#[tauri::command]
+pub async fn set_vault_ignore_patterns(patterns: Vec<String>) -> Result<(), String> {
+    let workdir = workdir().await?;
+    let mut content = patterns.join("\n");
+    content.push('\n');
+    write_atomic(&workdir.join(IGNORE_FILE), content.as_bytes()).map_err(|e| e.to_string())?;
+    reindex_vault().await;
+    Ok(())
+}
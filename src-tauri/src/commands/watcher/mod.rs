@@ -0,0 +1,161 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use notify_debouncer_full::{
+    DebounceEventResult, DebouncedEvent, Debouncer, RecommendedCache, new_debouncer,
+    notify::{
+        Event, EventKind, RecommendedWatcher, RecursiveMode,
+        event::{ModifyKind, RenameMode},
+    },
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+
+use super::{
+    IGNORE_FILE, entry_moved, entry_removed, entry_updated, is_ignored, reindex_vault,
+    relative_path,
+};
+
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+static WATCHER: Lazy<Mutex<Option<VaultWatcher>>> = Lazy::new(|| Mutex::new(None));
+
+struct VaultWatcher {
+    root: PathBuf,
+    // Dropping the debouncer stops the watch and closes the event channel.
+    _debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+}
+
+#[derive(Serialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum VaultChange {
+    Created { path: String },
+    Modified { path: String },
+    Removed { path: String },
+    Renamed { from: String, to: String },
+}
+
+// Relative path of an entry the indexes care about, i.e. one `.voidignore` keeps.
+fn vault_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = relative_path(root, path)?;
+    (!relative.is_empty() && !is_ignored(root, &relative, path.is_dir())).then_some(relative)
+}
+
+fn event_changes(root: &Path, event: &Event) -> Vec<VaultChange> {
+    if let EventKind::Modify(ModifyKind::Name(RenameMode::Both)) = event.kind {
+        let from = event.paths.first().and_then(|p| vault_path(root, p));
+        let to = event.paths.get(1).and_then(|p| vault_path(root, p));
+        return match (from, to) {
+            (Some(from), Some(to)) => vec![VaultChange::Renamed { from, to }],
+            (Some(path), None) => vec![VaultChange::Removed { path }],
+            (None, Some(path)) => vec![VaultChange::Created { path }],
+            (None, None) => Vec::new(),
+        };
+    }
+    event
+        .paths
+        .iter()
+        .filter_map(|file| {
+            let path = vault_path(root, file)?;
+            match event.kind {
+                EventKind::Create(_) => Some(VaultChange::Created { path }),
+                EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                    Some(VaultChange::Removed { path })
+                }
+                // The other half of the rename happened outside the vault or was not paired.
+                EventKind::Modify(ModifyKind::Name(_)) if file.exists() => {
+                    Some(VaultChange::Created { path })
+                }
+                EventKind::Modify(ModifyKind::Name(_)) => Some(VaultChange::Removed { path }),
+                EventKind::Modify(ModifyKind::Metadata(_)) => None,
+                EventKind::Modify(_) => Some(VaultChange::Modified { path }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+async fn handle_events(app: &AppHandle, root: &Path, events: Vec<DebouncedEvent>) {
+    let touches_ignore_file = events
+        .iter()
+        .flat_map(|event| event.paths.iter())
+        .any(|path| relative_path(root, path).as_deref() == Some(IGNORE_FILE));
+    if touches_ignore_file {
+        reindex_vault().await;
+    }
+    let mut changes = events
+        .iter()
+        .flat_map(|event| event_changes(root, event))
+        .collect::<Vec<_>>();
+    changes.dedup();
+    if changes.is_empty() {
+        return;
+    }
+    for change in &changes {
+        match change {
+            VaultChange::Created { path } | VaultChange::Modified { path } => {
+                entry_updated(root, path).await
+            }
+            VaultChange::Removed { path } => entry_removed(root, path).await,
+            VaultChange::Renamed { from, to } => entry_moved(root, from, to).await,
+        }
+    }
+    // One event per debounce tick, so listeners refresh once for a whole checkout.
+    let _ = app.emit("vault-changed", changes);
+}
+
+// Watches `root` for changes made outside the app (neovim, git, other editors).
+// Replaces the previous watch when the vault changes.
+pub fn watch_vault(app: AppHandle, root: PathBuf) -> Result<(), String> {
+    let mut watcher = WATCHER.lock().unwrap();
+    if watcher.as_ref().is_some_and(|w| w.root == root) {
+        return Ok(());
+    }
+    *watcher = None;
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let handler = move |result: DebounceEventResult| match result {
+        Ok(events) => {
+            let _ = sender.send(events);
+        }
+        Err(errors) => {
+            for e in errors {
+                eprintln!("Ошибка наблюдения за хранилищем: {}", e);
+            }
+        }
+    };
+    let mut debouncer = new_debouncer(DEBOUNCE, None, handler).map_err(|e| e.to_string())?;
+    debouncer
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| e.to_string())?;
+    let events_root = root.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(events) = receiver.recv().await {
+            handle_events(&app, &events_root, events).await;
+        }
+    });
+    *watcher = Some(VaultWatcher {
+        root,
+        _debouncer: debouncer,
+    });
+    Ok(())
+}
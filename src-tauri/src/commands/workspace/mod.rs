@@ -0,0 +1,334 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::{
+    io::Write,
+    path::{Component, Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use thiserror::Error;
+
+use super::{
+    forget_entry_links, forget_entry_quick_open, forget_entry_search, get_env, index_entry_links,
+    index_entry_quick_open, index_entry_search, mark_graph_dirty, reset_link_index,
+    reset_quick_open, reset_search_index, update_graph_entry, vault_ignore,
+};
+use crate::MAIN_FOLDER_PREFIX;
+
+#[derive(Error, Debug)]
+pub enum PathError {
+    #[error("рабочая директория не задана")]
+    NoWorkdir,
+
+    #[error("Путь `{0}` выходит за пределы хранилища")]
+    OutsideVault(String),
+
+    #[error("Ошибка доступа к пути: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<PathError> for String {
+    fn from(e: PathError) -> Self {
+        e.to_string()
+    }
+}
+
+pub struct VaultPath {
+    pub absolute: PathBuf,
+    pub relative: String,
+}
+
+pub async fn workdir() -> Result<PathBuf, PathError> {
+    let workdir = get_env("workdir".to_string())
+        .await
+        .map_err(|_| PathError::NoWorkdir)?;
+    if workdir.is_empty() {
+        return Err(PathError::NoWorkdir);
+    }
+    Ok(vault_root(&workdir))
+}
+
+// The one spelling of a vault folder that the watcher, the indexes and the vault registry
+// all compare against: canonical while it exists, so a symlinked or `/private/var` path
+// matches the paths notify reports. A folder that is gone keeps its given path.
+pub fn vault_root(path: &str) -> PathBuf {
+    match Path::new(path).canonicalize() {
+        Ok(path) => path,
+        Err(_) => PathBuf::from(path.trim_end_matches(['/', '\\'])),
+    }
+}
+
+// Absolute paths pointing into `root` are taken as they are; anything else, including the
+// frontend's "/note.md", is taken relative to `root`. The deepest existing ancestor is
+// canonicalized, so neither `..` nor symlinks can lead out of `root`.
+pub fn resolve_in(root: &Path, path: &str) -> Result<VaultPath, PathError> {
+    let outside = || PathError::OutsideVault(path.to_string());
+    let canonical = root.canonicalize()?;
+    let raw = Path::new(path);
+    let tail = [root, canonical.as_path()]
+        .into_iter()
+        .find_map(|r| raw.strip_prefix(r).ok())
+        .unwrap_or(raw);
+    let mut resolved = canonical.clone();
+    for component in tail.components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::ParentDir if resolved == canonical => return Err(outside()),
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir | Component::RootDir => {}
+            Component::Prefix(_) => return Err(outside()),
+        }
+    }
+    let mut existing = resolved.clone();
+    while existing.symlink_metadata().is_err() && existing != canonical {
+        existing.pop();
+    }
+    let real = existing.canonicalize()?;
+    if !real.starts_with(&canonical) {
+        return Err(outside());
+    }
+    let absolute = real.join(resolved.strip_prefix(&existing).unwrap_or(Path::new("")));
+    let relative = absolute
+        .strip_prefix(&canonical)
+        .map(|p| normalize_path(&p.to_string_lossy()))
+        .map_err(|_| outside())?;
+    Ok(VaultPath { absolute, relative })
+}
+
+pub async fn resolve_vault_path(path: &str) -> Result<VaultPath, PathError> {
+    resolve_in(&workdir().await?, path)
+}
+
+// Read-only commands may also reach into the application config directory.
+pub async fn resolve_readable_path(path: &str) -> Result<PathBuf, PathError> {
+    let config = MAIN_FOLDER_PREFIX
+        .get()
+        .filter(|config| Path::new(path).starts_with(config));
+    if let Some(config) = config {
+        return Ok(resolve_in(config, path)?.absolute);
+    }
+    Ok(resolve_vault_path(path).await?.absolute)
+}
+
+pub fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(normalize_path(relative.to_str()?))
+}
+
+pub fn is_under(path: &str, parent: &str) -> bool {
+    parent.is_empty()
+        || path == parent
+        || (path.starts_with(parent) && path.as_bytes().get(parent.len()) == Some(&b'/'))
+}
+
+// `name` inside the vault-relative folder `parent`, where "" is the vault root.
+pub fn child_path(parent: &str, name: &str) -> String {
+    match parent.is_empty() {
+        true => name.to_string(),
+        false => format!("{}/{}", parent, name),
+    }
+}
+
+// First free "name (label).ext", "name (label 2).ext", ... next to `path`.
+pub fn sibling_path(root: &Path, path: &str, is_dir: bool, label: &str) -> String {
+    let (dir, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (format!("{}/", dir), name),
+        None => (String::new(), path),
+    };
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !is_dir && !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|n| match n {
+            1 => format!("{}{} ({}){}", dir, stem, label, ext),
+            n => format!("{}{} ({} {}){}", dir, stem, label, n, ext),
+        })
+        .find(|candidate| root.join(candidate).symlink_metadata().is_err())
+        .unwrap()
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+pub fn write_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "некорректный путь",
+        ));
+    };
+    let temp = dir.join(format!(
+        ".{}.{}.tmp",
+        name.to_string_lossy(),
+        std::process::id()
+    ));
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp)?;
+        if let Ok(meta) = std::fs::metadata(path) {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.write_all(data)?;
+        file.sync_all()?;
+        std::fs::rename(&temp, path)?;
+        #[cfg(unix)]
+        std::fs::File::open(dir)?.sync_all()?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    result
+}
+
+// Files under `path` (relative to `root`), leaving out what `.voidignore` ignores.
+pub fn walk_files(root: &Path, path: &str) -> Vec<PathBuf> {
+    let matcher = vault_ignore(root);
+    let prefix = root.to_path_buf();
+    let ignored = move |file: &Path, is_dir: bool| {
+        relative_path(&prefix, file).is_some_and(|relative| {
+            !relative.is_empty()
+                && matcher
+                    .matched_path_or_any_parents(&relative, is_dir)
+                    .is_ignore()
+        })
+    };
+    let prune = ignored.clone();
+    jwalk::WalkDir::new(root.join(path))
+        .skip_hidden(false)
+        .process_read_dir(move |_, _, _, children| {
+            children.retain(|child| {
+                child
+                    .as_ref()
+                    .map(|c| !prune(&c.path(), c.file_type().is_dir()))
+                    .unwrap_or(true)
+            });
+        })
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.path())
+        .filter(|file| !ignored(file, false))
+        .collect()
+}
+
+pub async fn entry_updated(root: &Path, path: &str) {
+    index_entry_links(root, path);
+    index_entry_quick_open(root, path);
+    index_entry_search(root, path).await;
+    update_graph_entry(root, path).await;
+}
+
+pub async fn entry_removed(root: &Path, path: &str) {
+    forget_entry_links(root, path);
+    forget_entry_quick_open(root, path);
+    forget_entry_search(root, path).await;
+    update_graph_entry(root, path).await;
+}
+
+// Drops every index so the next access rebuilds it, e.g. after ignore rules change.
+pub async fn reindex_vault() {
+    reset_link_index();
+    reset_quick_open();
+    reset_search_index().await;
+    mark_graph_dirty();
+}
+
+pub async fn entry_moved(root: &Path, from: &str, to: &str) {
+    entry_removed(root, from).await;
+    entry_updated(root, to).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("void-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        root
+    }
+
+    #[test]
+    fn resolve_in_keeps_paths_inside_the_vault() {
+        let root = vault("resolve");
+        let resolved = resolve_in(&root, "./notes/./a.md").unwrap();
+        assert_eq!(resolved.relative, "notes/a.md");
+        let absolute = root.join("notes").join("b.md");
+        let resolved = resolve_in(&root, &absolute.to_string_lossy()).unwrap();
+        assert_eq!(resolved.relative, "notes/b.md");
+        assert_eq!(resolve_in(&root, "notes/../a.md").unwrap().relative, "a.md");
+        assert_eq!(resolve_in(&root, "/a.md").unwrap().relative, "a.md");
+        assert_eq!(
+            resolve_in(&root, "/notes/a.md").unwrap().relative,
+            "notes/a.md"
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn vault_root_sees_through_symlinks() {
+        let root = vault("symlinked");
+        let link = std::env::temp_dir().join(format!("void-link-{}", std::process::id()));
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&root, &link).unwrap();
+        let canonical = root.canonicalize().unwrap();
+        assert_eq!(vault_root(&link.to_string_lossy()), canonical);
+        assert_eq!(
+            vault_root(&format!("{}/", link.to_string_lossy())),
+            canonical
+        );
+        // notify reports canonical paths, which have to map back into the vault.
+        let event = canonical.join("notes").join("a.md");
+        let root_from_link = vault_root(&link.to_string_lossy());
+        assert_eq!(
+            relative_path(&root_from_link, &event).as_deref(),
+            Some("notes/a.md")
+        );
+        let _ = std::fs::remove_file(&link);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn resolve_in_rejects_paths_outside_the_vault() {
+        let root = vault("outside");
+        let escape = format!("{}/../a.md", root.to_string_lossy());
+        for path in ["../a.md", "notes/../../a.md", "/../a.md", escape.as_str()] {
+            assert!(
+                matches!(resolve_in(&root, path), Err(PathError::OutsideVault(_))),
+                "{}",
+                path
+            );
+        }
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -57,14 +57,21 @@ pub fn run() {
                 if let Err(e) = create_first_database(app.handle().clone()).await {
                     eprintln!("Ошибка при инициализации{}", e);
                 }
-                if get_env("workdir".to_string()).await.unwrap().as_str() != "" {
-                    let scope = app.fs_scope();
-                    let _ =
-                        scope.allow_directory(get_env("workdir".to_string()).await.unwrap(), true);
+                let workdir = get_env("workdir".to_string()).await.unwrap();
+                if !workdir.is_empty() {
+                    let opened = activate_vault(app.handle(), &workdir).await;
+                    if let Err(e) = opened {
+                        eprintln!("Ошибка при открытии хранилища{}", e);
+                    }
                 }
             });
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                forget_window_subscriptions(window.label());
+            }
+        })
         .manage(PtyState::default())
         .manage(video_state)
         .plugin(tauri_plugin_dialog::init())
@@ -110,7 +117,58 @@ pub fn run() {
             copy_font,
             get_all_user_fonts,
             get_app_config_dir,
-            get_absolute_path
+            get_absolute_path,
+            get_backlinks,
+            get_outgoing_links,
+            rebuild_link_index,
+            search_notes,
+            rebuild_search_index,
+            quick_open,
+            record_quick_open,
+            get_vault_graph,
+            get_shortest_path,
+            get_orphan_notes,
+            get_note_properties,
+            set_note_property,
+            remove_note_property,
+            get_note_history_enabled,
+            set_note_history_enabled,
+            list_note_versions,
+            get_note_at_version,
+            diff_note_versions,
+            restore_note_version,
+            configure_sync,
+            get_sync_status,
+            get_sync_conflicts,
+            sync_vault,
+            list_trash,
+            restore_from_trash,
+            empty_trash,
+            get_trash_retention,
+            set_trash_retention,
+            undo_last_fs_op,
+            redo_fs_op,
+            remove_entries,
+            rename_entries,
+            modify_entries,
+            get_ignore_patterns,
+            set_vault_ignore_patterns,
+            list_vaults,
+            add_vault,
+            remove_vault,
+            open_vault,
+            rename_vault,
+            get_vault_config,
+            set_vault_plugin,
+            get_setting,
+            set_setting,
+            set_vault_setting,
+            reset_setting,
+            export_settings,
+            import_settings,
+            get_database_status,
+            subscribe,
+            unsubscribe
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,345 @@
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use heck::ToUpperCamelCase;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Ident, Lit, LitStr, Meta, Path, Token,
+    Type, parse_macro_input, punctuated::Punctuated,
+};
+
+// Generated code lives inside the app crate, where the entity traits are
+// re-exported from `commands`; `#[entity(crate = "...")]` points it elsewhere.
+const TRAITS: &str = "crate::commands";
+
+struct EntityAttrs {
+    table: Option<LitStr>,
+    traits: Option<Path>,
+}
+
+struct EntityField {
+    ident: Ident,
+    // Local that collects the field in `new`, prefixed to stay clear of the function's own names.
+    slot: Ident,
+    key: String,
+    variant: Ident,
+    ty: Type,
+    // `None` when the field is required, otherwise how to fill it in when it is missing.
+    default: Option<TokenStream2>,
+    deserialize_with: Option<Path>,
+}
+
+// Derives `Entity` and `EntityControl` for a struct stored in SurrealDB,
+// together with a `<Name>Fields` enum holding one typed variant per field.
+//
+// #[derive(Serialize, Deserialize, Entity)]
+// #[entity(table = "plugins_repo")]
+// pub struct PluginList { ... }
+#[proc_macro_derive(Entity, attributes(entity))]
+pub fn derive_entity(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn entity_attrs(attrs: &[Attribute]) -> syn::Result<EntityAttrs> {
+    let mut parsed = EntityAttrs {
+        table: None,
+        traits: None,
+    };
+    for attr in attrs.iter().filter(|a| a.path().is_ident("entity")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                parsed.table = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("crate") {
+                parsed.traits = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("ожидается `table = \"...\"` или `crate = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(parsed)
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+fn string_value(expr: &Expr) -> Option<&LitStr> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Some(lit),
+        _ => None,
+    }
+}
+
+// Picks up the serde attributes that change how a missing or stored value is read,
+// so typed values passed to `from_key` go through the same path as database records.
+fn entity_field(field: &syn::Field) -> syn::Result<EntityField> {
+    let ident = field.ident.clone().unwrap();
+    let key = ident.to_string().trim_start_matches("r#").to_string();
+    let mut default = is_option(&field.ty).then(|| quote!(None));
+    let mut deserialize_with = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            match &meta {
+                Meta::Path(path) if path.is_ident("default") => {
+                    default = Some(quote!(::core::default::Default::default()));
+                }
+                Meta::NameValue(pair) if pair.path.is_ident("default") => {
+                    if let Some(lit) = string_value(&pair.value) {
+                        let path = lit.parse::<Path>()?;
+                        default = Some(quote!(#path()));
+                    }
+                }
+                Meta::NameValue(pair) if pair.path.is_ident("deserialize_with") => {
+                    if let Some(lit) = string_value(&pair.value) {
+                        deserialize_with = Some(lit.parse::<Path>()?);
+                    }
+                }
+                Meta::NameValue(pair) if pair.path.is_ident("with") => {
+                    if let Some(lit) = string_value(&pair.value) {
+                        let module = lit.parse::<Path>()?;
+                        deserialize_with = Some(syn::parse_quote!(#module::deserialize));
+                    }
+                }
+                Meta::NameValue(pair) if pair.path.is_ident("rename") => {
+                    return Err(syn::Error::new_spanned(
+                        pair,
+                        "Entity не поддерживает переименование полей",
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(EntityField {
+        variant: format_ident!("{}", key.to_upper_camel_case()),
+        slot: format_ident!("field_{}", key),
+        ident,
+        key,
+        ty: field.ty.clone(),
+        default,
+        deserialize_with,
+    })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let attrs = entity_attrs(&input.attrs)?;
+    let table = attrs
+        .table
+        .ok_or_else(|| syn::Error::new_spanned(name, "нужен атрибут #[entity(table = \"...\")]"))?;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(entity_field)
+                .collect::<syn::Result<Vec<_>>>()?,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Entity работает только со структурами с именованными полями",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Entity работает только со структурами",
+            ));
+        }
+    };
+    // `type` and `type_` would both become `Type` in the fields enum.
+    for (i, field) in fields.iter().enumerate() {
+        if let Some(first) = fields[..i].iter().find(|f| f.variant == field.variant) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                format!(
+                    "Поля {} и {} совпадают в {}Fields",
+                    first.key, field.key, name
+                ),
+            ));
+        }
+    }
+    let traits = match attrs.traits {
+        Some(traits) => traits,
+        None => syn::parse_str::<Path>(TRAITS)?,
+    };
+    let fields_enum = format_ident!("{}Fields", name);
+
+    let variants = fields.iter().map(|f| {
+        let (variant, ty) = (&f.variant, &f.ty);
+        quote!(#variant(#ty))
+    });
+    let metas = fields.iter().map(|f| {
+        let (key, required) = (&f.key, f.default.is_none());
+        let ty = f.ty.to_token_stream().to_string().replace(' ', "");
+        quote! {
+            #traits::FieldMeta {
+                key: #key,
+                ty: #ty,
+                required: #required,
+            }
+        }
+    });
+    let keys = fields.iter().map(|f| {
+        let (variant, key) = (&f.variant, &f.key);
+        quote!(#fields_enum::#variant(_) => #key)
+    });
+    let values = fields.iter().map(|f| {
+        let (variant, key) = (&f.variant, &f.key);
+        quote! {
+            #fields_enum::#variant(value) => ::serde_json::to_value(value)
+                .map_err(|_| #traits::EntityError::InvalidField(#key))
+        }
+    });
+    let parsers = fields.iter().map(|f| {
+        let (variant, key, ty) = (&f.variant, &f.key, &f.ty);
+        let parse = match &f.deserialize_with {
+            Some(path) => quote!(#path(value)),
+            None => quote!(<#ty as ::serde::Deserialize>::deserialize(value)),
+        };
+        quote! {
+            #key => #parse
+                .map(#fields_enum::#variant)
+                .map_err(|_| #traits::EntityError::InvalidField(#key))
+        }
+    });
+    let slots = fields.iter().map(|f| {
+        let (slot, ty) = (&f.slot, &f.ty);
+        quote!(let mut #slot: ::core::option::Option<#ty> = None;)
+    });
+    let collect = fields.iter().map(|f| {
+        let (slot, variant) = (&f.slot, &f.variant);
+        let repeated = format!("Поле {} указано дважды", f.key);
+        quote! {
+            #fields_enum::#variant(value) => {
+                if #slot.replace(value).is_some() {
+                    return Err(<#name as #traits::EntityControl<#fields_enum, #name>>::throw_error(
+                        app.clone(),
+                        #repeated,
+                    ));
+                }
+            }
+        }
+    });
+    let build = fields.iter().map(|f| {
+        let (ident, slot) = (&f.ident, &f.slot);
+        match &f.default {
+            Some(default) => quote!(#ident: #slot.unwrap_or_else(|| #default)),
+            None => {
+                let missing = format!("Некорректное поле {}", f.key);
+                quote! {
+                    #ident: match #slot {
+                        Some(value) => value,
+                        None => {
+                            return Err(<#name as #traits::EntityControl<#fields_enum, #name>>::throw_error(
+                                app.clone(),
+                                #missing,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    });
+    let getters = fields.iter().map(|f| {
+        let (ident, key) = (&f.ident, &f.key);
+        quote! {
+            #key => ::serde_json::to_value(&self.#ident)
+                .map_err(|_| #traits::EntityError::InvalidField(#key))
+        }
+    });
+
+    Ok(quote! {
+        #vis enum #fields_enum {
+            #(#variants,)*
+        }
+
+        impl #traits::Entity for #name {
+            type Fields = #fields_enum;
+            const TABLE: &'static str = #table;
+            const FIELDS: &'static [#traits::FieldMeta] = &[#(#metas,)*];
+        }
+
+        impl #traits::EntityField for #fields_enum {
+            fn key(&self) -> &'static str {
+                match self {
+                    #(#keys,)*
+                }
+            }
+
+            fn value(&self) -> ::core::result::Result<::serde_json::Value, #traits::EntityError> {
+                match self {
+                    #(#values,)*
+                }
+            }
+
+            fn from_key(
+                key: &str,
+                value: ::serde_json::Value,
+            ) -> ::core::result::Result<Self, #traits::EntityError> {
+                match key {
+                    #(#parsers,)*
+                    _ => Err(#traits::EntityError::NotFound),
+                }
+            }
+        }
+
+        impl #traits::EntityControl<#fields_enum, #name> for #name {
+            fn new(
+                input: ::std::vec::Vec<#fields_enum>,
+                app: #traits::AppHandle,
+            ) -> ::core::result::Result<#name, #traits::EntityError> {
+                #(#slots)*
+                for field in input {
+                    match field {
+                        #(#collect)*
+                    }
+                }
+                Ok(#name {
+                    #(#build,)*
+                })
+            }
+
+            fn get_value(
+                &self,
+                key: &str,
+            ) -> ::core::result::Result<::serde_json::Value, #traits::EntityError> {
+                match key {
+                    #(#getters,)*
+                    _ => Err(#traits::EntityError::NotFound),
+                }
+            }
+        }
+    })
+}
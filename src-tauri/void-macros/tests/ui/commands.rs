@@ -0,0 +1,37 @@
+// Stand-ins for the app's entity traits, shaped like the ones in `commands`.
+use serde_json::Value;
+
+#[derive(Clone)]
+pub struct AppHandle;
+
+#[derive(Debug, PartialEq)]
+pub enum EntityError {
+    InvalidField(&'static str),
+    NotFound,
+}
+
+pub struct FieldMeta {
+    pub key: &'static str,
+    pub ty: &'static str,
+    pub required: bool,
+}
+
+pub trait Entity {
+    type Fields: EntityField;
+    const TABLE: &'static str;
+    const FIELDS: &'static [FieldMeta];
+}
+
+pub trait EntityField: Sized {
+    fn key(&self) -> &'static str;
+    fn value(&self) -> Result<Value, EntityError>;
+    fn from_key(key: &str, value: Value) -> Result<Self, EntityError>;
+}
+
+pub trait EntityControl<B, T> {
+    fn new(input: Vec<B>, app: AppHandle) -> Result<T, EntityError>;
+    fn get_value(&self, key: &str) -> Result<Value, EntityError>;
+    fn throw_error(_app: AppHandle, err_val: &'static str) -> EntityError {
+        EntityError::InvalidField(err_val)
+    }
+}
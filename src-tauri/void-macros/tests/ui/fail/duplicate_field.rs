@@ -0,0 +1,10 @@
+use void_macros::Entity;
+
+#[derive(Entity)]
+#[entity(table = "note")]
+pub struct Note {
+    pub r#type: String,
+    pub type_: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use void_macros::Entity;
+
+#[derive(Entity)]
+pub struct Note {
+    pub name: String,
+}
+
+fn main() {}
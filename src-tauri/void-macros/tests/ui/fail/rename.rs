@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use void_macros::Entity;
+
+#[derive(Serialize, Deserialize, Entity)]
+#[entity(table = "note")]
+pub struct Note {
+    #[serde(rename = "title")]
+    pub name: String,
+}
+
+fn main() {}
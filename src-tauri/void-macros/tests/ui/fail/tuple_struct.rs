@@ -0,0 +1,7 @@
+use void_macros::Entity;
+
+#[derive(Entity)]
+#[entity(table = "note")]
+pub struct Note(String);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use void_macros::Entity;
+
+#[derive(Entity)]
+#[entity(table = "note", name = "notes")]
+pub struct Note {
+    pub name: String,
+}
+
+fn main() {}
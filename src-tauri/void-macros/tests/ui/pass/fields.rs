@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use void_macros::Entity;
+
+mod commands {
+    include!("../commands.rs");
+}
+
+use commands::{AppHandle, Entity, EntityControl, EntityError, EntityField};
+
+fn pinned() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Entity)]
+#[entity(table = "note")]
+pub struct Note {
+    pub name: String,
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default = "pinned")]
+    pub pinned: bool,
+}
+
+fn main() {
+    assert_eq!(Note::TABLE, "note");
+    let required = Note::FIELDS
+        .iter()
+        .map(|f| (f.key, f.ty, f.required))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        required,
+        [
+            ("name", "String", true),
+            ("tags", "Option<Vec<String>>", false),
+            ("size", "u64", false),
+            ("pinned", "bool", false),
+        ]
+    );
+
+    let note = Note::new(vec![NoteFields::Name("a".into())], AppHandle).unwrap();
+    assert_eq!(note.tags, None);
+    assert_eq!(note.size, 0);
+    assert!(note.pinned);
+    assert_eq!(note.get_value("name"), Ok(json!("a")));
+    assert_eq!(note.get_value("body"), Err(EntityError::NotFound));
+
+    let missing = Note::new(vec![NoteFields::Size(1)], AppHandle);
+    assert_eq!(
+        missing.err(),
+        Some(EntityError::InvalidField("Некорректное поле name"))
+    );
+
+    let twice = Note::new(
+        vec![NoteFields::Name("a".into()), NoteFields::Name("b".into())],
+        AppHandle,
+    );
+    assert_eq!(
+        twice.err(),
+        Some(EntityError::InvalidField("Поле name указано дважды"))
+    );
+
+    let field = NoteFields::from_key("size", json!(3)).unwrap();
+    assert_eq!(field.key(), "size");
+    assert_eq!(field.value(), Ok(json!(3)));
+    assert_eq!(
+        NoteFields::from_key("size", json!("3")).err(),
+        Some(EntityError::InvalidField("size"))
+    );
+}
@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use void_macros::Entity;
+
+mod store {
+    include!("../commands.rs");
+}
+
+use store::{EntityError, EntityField};
+
+// JSON objects only take string keys, so this map can't be written.
+#[derive(Serialize, Deserialize, Entity)]
+#[entity(table = "grid", crate = "crate::store")]
+pub struct Grid {
+    pub cells: HashMap<(u8, u8), u8>,
+}
+
+fn main() {
+    let cells = HashMap::from([((0, 0), 1)]);
+    assert_eq!(
+        GridFields::Cells(cells).value(),
+        Err(EntityError::InvalidField("cells"))
+    );
+}
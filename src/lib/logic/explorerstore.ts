@@ -31,6 +31,10 @@ export const useExplorerStore = defineStore('explorer', {
         console.log(this.current);
       }
       localStorage.setItem('mindbreaker:explorer', this.current)
+    },
+    reset() {
+      this.current = '';
+      localStorage.setItem('mindbreaker:explorer', this.current)
     }
   }
 })
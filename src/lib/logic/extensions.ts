@@ -30,9 +30,10 @@ export type Plugin = {
   plugin_autor: string,
   plugin_version: string,
   plugin_type: string,
-  plugin_link: string,
-  is_installed: string,
-  is_enabled: string
+  plugin_link: string | null,
+  is_installed: boolean,
+  is_enabled: boolean,
+  installed_at: number | null
 }
 
 export async function add_extension_tables(link: string) {
@@ -51,8 +52,16 @@ export async function add_extension_tables(link: string) {
   }
 }
 
-export async function get_plugins_list(key: string): Promise<Plugin[]> {
-  let list = await invoke<Plugin[]>('get_list_of_plugins', { key: key });
+export type ListQuery = {
+  filters?: { key: string, cmp?: 'eq' | 'ne' | 'lt' | 'le' | 'gt' | 'ge' | 'contains', value: unknown }[],
+  order_by?: string,
+  order?: 'asc' | 'desc',
+  limit?: number,
+  offset?: number
+}
+
+export async function get_plugins_list(key: string, query?: ListQuery): Promise<Plugin[]> {
+  let list = await invoke<Plugin[]>('get_list_of_plugins', { key: key, query: query });
   return list;
 }
 
@@ -61,14 +70,7 @@ export async function install_plugin(key: string) {
 }
 
 export async function changePluginState(plug_name: string, prev_val: boolean) {
-  let val: string;
-  if (prev_val) {
-    val = 'false';
-  }
-  else {
-    val = 'true';
-  }
-  await invoke('operate_plugin', { plugName: plug_name, val: val });
+  await invoke('operate_plugin', { plugName: plug_name, val: !prev_val });
 }
 
 export async function get_official_plugin(plug_name: string): Promise<any> {
@@ -1,9 +1,19 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export async function get_note(path: String): Promise<string> {
+export interface NoteContent {
+  content: string;
+  token: string;
+}
+
+export type WriteResult =
+  | { status: 'written'; token: string }
+  | { status: 'merged'; token: string; content: string }
+  | { status: 'conflict'; token: string; content: string };
+
+export async function get_note(path: String): Promise<NoteContent> {
   return await invoke('get_note_content', { path: path });
 }
 
-export async function write_note(path: String, content: String) {
-  await invoke('write_note_changes', { path: path, value: content })
+export async function write_note(path: String, content: String, token?: string): Promise<WriteResult> {
+  return await invoke('write_note_changes', { path: path, value: content, token: token })
 }
@@ -25,7 +25,14 @@ export type Theme = {
   theme_author: string,
   theme_version: string,
   theme_link: string,
-  is_installed: string,
+  is_installed: boolean,
+  installed_at: number | null,
+}
+
+export type Vault = {
+  name: string,
+  path: string,
+  last_opened: number,
 }
 
 export type SideRepo = {
@@ -47,23 +54,75 @@ export async function changeWorkdir(): Promise<string> {
     error = "path not provided";
     return error;
   }
-  await invoke('set_env', { ename: 'workdir', name: directory });
+  await invoke('add_vault', { path: directory });
+  await open_vault(directory);
   await invoke('setup_config_directory')
-  localStorage.setItem('mindbreaker:explorer', '');
   return directory;
 }
 
+export async function list_vaults(): Promise<Vault[]> {
+  return await invoke<Vault[]>('list_vaults');
+}
+
+export async function open_vault(path: string): Promise<Vault> {
+  const vault = await invoke<Vault>('open_vault', { path: path });
+  localStorage.setItem('mindbreaker:explorer', '');
+  return vault;
+}
+
 export async function getWorkdir(): Promise<string> {
   const directory: string = await invoke('get_env', { ename: 'workdir' });
   return directory;
 }
 
+export type Setting = {
+  key: string,
+  type: 'bool' | 'integer' | 'text' | 'choice' | 'list',
+  default: unknown,
+  min?: number,
+  max?: number,
+  options?: string[],
+  value: unknown,
+  source: 'default' | 'user' | 'vault',
+}
+
+export async function get_setting(key: string): Promise<Setting> {
+  return await invoke<Setting>('get_setting', { key: key });
+}
+
+export async function set_setting(key: string, value: unknown): Promise<Setting> {
+  return await invoke<Setting>('set_setting', { key: key, value: value });
+}
+
+export async function reset_setting(key: string): Promise<Setting> {
+  return await invoke<Setting>('reset_setting', { key: key });
+}
+
+export type VaultConfig = {
+  settings: Record<string, unknown>,
+  plugins: Record<string, boolean>,
+}
+
+export async function get_vault_config(): Promise<VaultConfig> {
+  return await invoke<VaultConfig>('get_vault_config');
+}
+
+// Leaving out `value` removes the vault's own value.
+export async function set_vault_setting(key: string, value?: unknown): Promise<Setting> {
+  return await invoke<Setting>('set_vault_setting', { key: key, value: value });
+}
+
+export async function set_vault_plugin(name: string, enabled?: boolean): Promise<VaultConfig> {
+  return await invoke<VaultConfig>('set_vault_plugin', { name: name, enabled: enabled });
+}
+
 export async function getUsername(): Promise<string> {
   const uname: string = await invoke('get_env', { ename: 'name' });
   return uname;
 }
 
-export async function set_theme(theme_name: string) {
+// Shows `theme_name` without saving it, e.g. when a vault pins its own theme.
+export async function apply_theme(theme_name: string) {
   const theme_store = useThemeStore();
 
   const existing = document.getElementById("custom_theme");
@@ -81,6 +140,24 @@ export async function set_theme(theme_name: string) {
   theme_store.change_theme(theme_name);
 }
 
+// The theme is the `appearance.theme` setting, so the open vault may override it.
+export async function load_theme() {
+  let setting = await get_setting('appearance.theme');
+  let legacy = localStorage.getItem('mindbreaker:theme');
+  if (legacy != null) {
+    localStorage.removeItem('mindbreaker:theme');
+    if (setting.source == 'default') {
+      setting = await set_setting('appearance.theme', legacy);
+    }
+  }
+  await apply_theme(setting.value as string);
+}
+
+export async function set_theme(theme_name: string) {
+  let setting = await set_setting('appearance.theme', theme_name);
+  await apply_theme(setting.value as string);
+}
+
 export function get_themes_marketplace(object: any[]): any[] {
   return object;
 }
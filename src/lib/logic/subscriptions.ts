@@ -0,0 +1,58 @@
+
+/**
+ * Copyright 2025 The VOID Authors. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { ListQuery } from "./extensions";
+
+export type Topic = 'plugins' | 'themes' | 'repos' | 'vaults' | 'settings';
+
+export type EntityChange<T> = {
+  subscription: number,
+  table: string,
+  action: 'create' | 'update' | 'delete',
+  record: T
+}
+
+// Changes arrive only in the window that subscribed; the backend drops the
+// subscription by itself when that window is closed. The current records are
+// sent first as creations, usually before `subscribe` resolves, so events are
+// held until the subscription id is known.
+export async function subscribe<T>(topic: Topic, onChange: (change: EntityChange<T>) => void, query?: ListQuery): Promise<() => Promise<void>> {
+  let id: number | null = null;
+  let pending: EntityChange<T>[] = [];
+  let unlisten = await getCurrentWebviewWindow().listen<EntityChange<T>>('entity-changed', (event) => {
+    if (id === null) {
+      pending.push(event.payload);
+    } else if (event.payload.subscription === id) {
+      onChange(event.payload);
+    }
+  });
+  try {
+    id = await invoke<number>('subscribe', { topic: topic, query: query });
+  } catch (e) {
+    unlisten();
+    throw e;
+  }
+  for (let change of pending.filter((change) => change.subscription === id)) {
+    onChange(change);
+  }
+  pending = [];
+  return async () => {
+    unlisten();
+    await invoke('unsubscribe', { id: id });
+  };
+}
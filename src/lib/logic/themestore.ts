@@ -20,7 +20,6 @@ export const useThemeStore = defineStore('theme', {
   actions: {
     change_theme(name: string) {
       this.current = name;
-      localStorage.setItem('mindbreaker:theme', name)
     }
   }
 })
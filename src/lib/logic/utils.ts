@@ -17,7 +17,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import { Router } from "vue-router";
 import { useExplorerStore } from "./explorerstore";
-import { readFile, readTextFile } from "@tauri-apps/plugin-fs";
 
 type AudioMeta = {
   picture: Uint8Array,
@@ -33,7 +32,23 @@ type AudioResponse = {
 
 export type Entry = {
   name: string,
-  entry_type: string
+  entry_type: string,
+  path: string,
+  kind: 'folder' | 'note' | 'canvas' | 'image' | 'audio' | 'video' | 'pdf' | 'other',
+  size: number,
+  created: number | null,
+  modified: number | null,
+  children: number | null,
+  hidden: boolean,
+  entries: Entry[] | null
+}
+
+export type ListOptions = {
+  sort?: 'name' | 'modified' | 'created' | 'size' | 'kind',
+  descending?: boolean,
+  folders_first?: boolean,
+  show_hidden?: boolean,
+  depth?: number
 }
 
 export async function getFullPath(subPath: string): Promise<string> {
@@ -43,11 +58,7 @@ export async function getFullPath(subPath: string): Promise<string> {
 export async function get_file_content(
   path: string,
 ): Promise<string> {
-  //let bytes: Uint8Array = await invoke("get_file", { ipath: path });
-  //let url = URL.createObjectURL(
-  //  new Blob([new Uint8Array(bytes)], { type: intype }),
-  //);
-  const binary = await readFile(path);
+  const binary = await invoke<ArrayBuffer>("get_file", { ipath: path });
   const blob = new Blob([new Uint8Array(binary)]);
   const url = URL.createObjectURL(blob);
   return url;
@@ -73,8 +84,8 @@ export function checkShowable(): boolean {
   }
 }
 
-export async function get_folder_content(dirname: string): Promise<Entry[]> {
-  return await invoke<Entry[]>("get_directory_content", { dirname: dirname });
+export async function get_folder_content(dirname: string, options?: ListOptions): Promise<Entry[]> {
+  return await invoke<Entry[]>("get_directory_content", { dirname: dirname, options: options });
 }
 
 export async function create_file(name: string, dirname: string) {
@@ -142,14 +153,34 @@ export async function write_canvas(data: string, path?: string): Promise<string>
 }
 
 export async function read_canvas(path: string): Promise<string> {
-  let data = await readTextFile(path);
-  return data;
+  let data = await invoke<ArrayBuffer>("get_file", { ipath: path });
+  return new TextDecoder().decode(data);
 }
 
 export async function get_env(key: string): Promise<string> {
   return await invoke('get_env', { ename: key });
 }
 
-export async function copy_entry(before_path: string, after_path: string, flag: string) {
-  await invoke('modify_entry', { beforePath: before_path, afterPath: after_path, flag: flag });
+export type ConflictPolicy = 'fail' | 'skip' | 'overwrite' | 'keep_both';
+
+export async function copy_entry(before_path: string, after_path: string, flag: string, conflict?: ConflictPolicy) {
+  await invoke('modify_entry', { beforePath: before_path, afterPath: after_path, flag: flag, conflict: conflict });
+}
+
+export interface BatchReport {
+  items: { path: string; ok: boolean; error: string | null; changed: string[] }[];
+  succeeded: number;
+  failed: number;
+}
+
+export async function remove_entries(paths: string[]): Promise<BatchReport> {
+  return await invoke('remove_entries', { paths: paths });
+}
+
+export async function rename_entries(entries: { path: string; new_name: string }[]): Promise<BatchReport> {
+  return await invoke('rename_entries', { entries: entries });
+}
+
+export async function copy_entries(before_paths: string[], after_path: string, flag: string, conflict?: ConflictPolicy): Promise<BatchReport> {
+  return await invoke('modify_entries', { beforePaths: before_paths, afterPath: after_path, flag: flag, conflict: conflict });
 }